@@ -2,7 +2,48 @@
 use godot::prelude::*;
 use uuid::Uuid;
 
-use crate::Furniture;
+use crate::{Furniture, NeedDecay};
+
+/// Who wanted this action, and how badly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ActionPriority {
+    /// Chosen by [crate::PersonAi]. Urgent needs may preempt these.
+    #[default]
+    Autonomous,
+    /// Queued by the player.
+    Player,
+    /// Forced by a critical need. Can't be cancelled.
+    Forced,
+}
+
+/// Part played in a group activity of more than two people.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupRole {
+    /// Runs the activity, takes slot 0.
+    Leader {
+        /// How many people fit, leader included.
+        slots: usize,
+        /// Invited people who haven't left.
+        members: Vec<Uuid>,
+        /// How many members accepted the invite, see [GroupRole::has_quorum].
+        joined: usize,
+    },
+    /// Joined the leader's activity.
+    Member { leader_uuid: Uuid, slot: usize },
+}
+
+impl GroupRole {
+    /// Enough members remain to carry on: at least half of those who joined, rounded up, and
+    /// always at least one. Members can't tell, so they always have quorum.
+    pub fn has_quorum(&self) -> bool {
+        match self {
+            GroupRole::Leader {
+                members, joined, ..
+            } => members.len() >= joined.div_ceil(2).max(1),
+            GroupRole::Member { .. } => true,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Action {
@@ -12,6 +53,9 @@ pub struct Action {
     pub partner_uuid: Option<Uuid>,
     /// (Pair activity), Master copy of the task is
     pub master_uuid: Option<Uuid>,
+    /// Group activity role, for more than two people
+    pub group: Option<GroupRole>,
+    pub priority: ActionPriority,
 }
 
 impl Action {
@@ -21,6 +65,20 @@ impl Action {
             object: None,
             partner_uuid: None,
             master_uuid: None,
+            group: None,
+            priority: ActionPriority::Autonomous,
+        }
+    }
+
+    /// An action that's performed on the spot, without an object.
+    pub fn in_place(key: &str) -> Self {
+        Self {
+            key: key.into(),
+            object: None,
+            partner_uuid: None,
+            master_uuid: None,
+            group: None,
+            priority: ActionPriority::Autonomous,
         }
     }
 
@@ -28,6 +86,95 @@ impl Action {
         self.master_uuid.is_none()
     }
 
+    /// Done with another person, without furniture.
+    pub fn is_social(&self) -> bool {
+        self.object.is_none() && self.partner_uuid.is_some()
+    }
+
+    /// Everyone else taking part, as far as this copy of the action knows.
+    pub fn company(&self) -> Vec<Uuid> {
+        match &self.group {
+            Some(GroupRole::Leader { members, .. }) => members.clone(),
+            Some(GroupRole::Member { leader_uuid, .. }) => vec![*leader_uuid],
+            None => self.partner_uuid.into_iter().collect(),
+        }
+    }
+
+    pub fn can_be_cancelled(&self) -> bool {
+        self.priority != ActionPriority::Forced
+    }
+
+    /// The need this action is mainly about, if any. Actions that change an object rather than
+    /// the person, like cleaning up, have none and always run their full duration.
+    pub fn primary_need(&self) -> Option<&'static str> {
+        Self::primary_need_of(&self.key)
+    }
+
+    /// [Self::primary_need] of an action by its key.
+    pub fn primary_need_of(key: &str) -> Option<&'static str> {
+        match key {
+            "make_food" => Some("hunger"),
+            "toilet" => Some("bladder"),
+            "sit" => Some("comfort"),
+            "sleep" | "pass_out" => Some("sleep"),
+            "do_the_mario" => Some("fun"),
+            "wash_hands" => Some("hygiene"),
+            "eat_together" => Some("hunger"),
+            "chat" | "hug" | "group_chat" => Some("social"),
+            "joke" => Some("fun"),
+            _ => None,
+        }
+    }
+
+    /// Seconds this action can last at most.
+    pub fn max_duration(&self) -> f64 {
+        match self.key.as_str() {
+            "make_food" => 20.0,
+            "toilet" => 10.0,
+            "sit" => 15.0,
+            "sleep" => 120.0,
+            "do_the_mario" => 15.0,
+            "wash_hands" => 6.0,
+            "clean_puddle" => 8.0,
+            "light_on" | "light_off" => 2.0,
+            "chat" => 15.0,
+            "eat_together" => 20.0,
+            "group_chat" => 20.0,
+            "joke" => 8.0,
+            "hug" => 4.0,
+            "argue" => 10.0,
+            "pass_out" => 60.0,
+            "accident" => 4.0,
+            "feel_gross" => 3.0,
+            "sulk" => 4.0,
+            "idle" => 5.0,
+            _ => 8.0,
+        }
+    }
+
+    /// Short imperative label, for menus.
+    pub fn to_menu_label(&self) -> String {
+        match self.key.as_str() {
+            "make_food" => "Make food".into(),
+            "toilet" => "Use toilet".into(),
+            "sit" => "Sit".into(),
+            "sleep" => "Sleep".into(),
+            "do_the_mario" => "Do the Mario".into(),
+            "wash_hands" => "Wash hands".into(),
+            "clean_puddle" => "Clean up".into(),
+            "light_on" => "Turn on".into(),
+            "light_off" => "Turn off".into(),
+            "chat" => "Chat".into(),
+            "eat_together" => "Have dinner".into(),
+            "group_chat" => "Group chat".into(),
+            "joke" => "Tell a joke".into(),
+            "hug" => "Hug".into(),
+            "argue" => "Argue".into(),
+            "idle" => "Idle".into(),
+            _ => self.key.clone(),
+        }
+    }
+
     pub fn to_present_tense(&self) -> String {
         match self.key.as_str() {
             "make_food" => "Making food".into(),
@@ -35,8 +182,85 @@ impl Action {
             "sit" => "Sitting".into(),
             "sleep" => "Sleeping".into(),
             "do_the_mario" => "Doing the Mario".into(),
+            "wash_hands" => "Washing hands".into(),
+            "clean_puddle" => "Cleaning up".into(),
+            "light_on" => "Turning on the light".into(),
+            "light_off" => "Turning off the light".into(),
+            "chat" => "Chatting".into(),
+            "eat_together" => "Having dinner".into(),
+            "group_chat" => "Chatting in a group".into(),
+            "joke" => "Joking around".into(),
+            "hug" => "Hugging".into(),
+            "argue" => "Arguing".into(),
+            "pass_out" => "Passed out".into(),
+            "accident" => "Having an accident".into(),
+            "feel_gross" => "Feeling gross".into(),
+            "sulk" => "Sulking".into(),
             "idle" => "Idling".into(),
             _ => format!("Unknown activity: `{}`", self.key),
         }
     }
+
+    /// How performing this action affects the decay of needs.
+    pub fn need_decay(&self) -> NeedDecay {
+        match self.key.as_str() {
+            "sleep" | "pass_out" => NeedDecay {
+                bladder: 0.5,
+                fun: 0.0,
+                hunger: 0.5,
+                sleep: 0.0,
+                social: 0.0,
+                ..Default::default()
+            },
+            "sit" => NeedDecay {
+                comfort: 0.0,
+                ..Default::default()
+            },
+            "toilet" => NeedDecay {
+                bladder: 0.0,
+                ..Default::default()
+            },
+            "chat" | "joke" | "hug" | "argue" | "group_chat" | "eat_together" => NeedDecay {
+                social: 0.0,
+                ..Default::default()
+            },
+            "do_the_mario" => NeedDecay {
+                hunger: 1.5,
+                hygiene: 2.0,
+                social: 0.0,
+                ..Default::default()
+            },
+            _ => NeedDecay::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leader(members: usize, joined: usize) -> GroupRole {
+        GroupRole::Leader {
+            slots: 6,
+            members: (0..members).map(|_| Uuid::new_v4()).collect(),
+            joined,
+        }
+    }
+
+    #[test]
+    fn group_needs_half_of_those_who_joined() {
+        assert!(leader(4, 4).has_quorum());
+        assert!(leader(2, 4).has_quorum());
+        assert!(!leader(1, 4).has_quorum());
+
+        assert!(leader(2, 3).has_quorum());
+        assert!(!leader(1, 3).has_quorum());
+    }
+
+    #[test]
+    fn group_needs_someone() {
+        assert!(leader(1, 1).has_quorum());
+        assert!(!leader(0, 1).has_quorum());
+        assert!(!leader(0, 0).has_quorum());
+    }
 }
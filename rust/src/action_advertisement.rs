@@ -1,13 +1,14 @@
 // SPDX-License-Identifier: LGPL-3.0-or-later
 use godot::prelude::*;
+use uuid::Uuid;
 
 use crate::Furniture;
 
 /// Describes an action a Person can perform on an entity.
 #[derive(Debug, Clone)]
 pub struct ActionAdvertisement {
-    /// The object responsible for this advertisement
-    pub source_node: Gd<Furniture>,
+    /// The entity responsible for this advertisement
+    pub source: ActionAdvertisementSource,
     /// String key of the action
     pub action_key: String,
     /// What kind of needs does this action appel
@@ -16,6 +17,13 @@ pub struct ActionAdvertisement {
     pub required_people: usize,
 }
 
+#[derive(Debug, Clone)]
+pub enum ActionAdvertisementSource {
+    Furniture(Gd<Furniture>),
+    /// A person advertises social actions, with themselves as the partner.
+    Person(Uuid),
+}
+
 #[derive(Debug, Clone)]
 pub struct ActionAdvertisementStat {
     /// String key of the need this advertisement promises to satisfy.
@@ -23,3 +31,13 @@ pub struct ActionAdvertisementStat {
     /// Higher means more desireable. Zero means no effect. Can be negative for discouragement.
     pub value: isize,
 }
+
+impl ActionAdvertisement {
+    /// The furniture this action is performed on, if any.
+    pub fn furniture(&self) -> Option<&Gd<Furniture>> {
+        match &self.source {
+            ActionAdvertisementSource::Furniture(furniture) => Some(furniture),
+            ActionAdvertisementSource::Person(_) => None,
+        }
+    }
+}
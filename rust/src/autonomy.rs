@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use godot::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ActionPriority;
+
+/// How much a [crate::Person] does on their own.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, GodotConvert, Var, Export, Serialize, Deserialize,
+)]
+#[godot(via = GString)]
+pub enum Autonomy {
+    /// Idle unless the player queues something.
+    Off,
+    /// Only act on their own when a need is critical.
+    Low,
+    #[default]
+    Full,
+}
+
+impl Autonomy {
+    pub const ALL: [Autonomy; 3] = [Autonomy::Off, Autonomy::Low, Autonomy::Full];
+
+    pub fn to_label(self) -> &'static str {
+        match self {
+            Autonomy::Off => "Off",
+            Autonomy::Low => "Low",
+            Autonomy::Full => "Full",
+        }
+    }
+
+    /// Can an urgent need interrupt a task of this priority.
+    pub fn can_preempt(self, priority: ActionPriority) -> bool {
+        match self {
+            Autonomy::Off => false,
+            Autonomy::Low => priority == ActionPriority::Autonomous,
+            Autonomy::Full => priority != ActionPriority::Forced,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use serde::{Deserialize, Serialize};
+
+use crate::NeedDecay;
+
+/// A temporary effect on a [crate::Person]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Buff {
+    pub key: String,
+    /// Seconds until the buff wears off
+    pub time_left: f64,
+    pub decay: NeedDecay,
+    /// Added to mood while active
+    pub mood: f64,
+}
+
+impl Buff {
+    /// Slept well, gets tired slower for a while.
+    pub fn well_rested() -> Self {
+        Self {
+            key: "well_rested".into(),
+            time_left: 120.0,
+            decay: NeedDecay {
+                sleep: 0.5,
+                ..Default::default()
+            },
+            mood: 0.1,
+        }
+    }
+
+    /// Got turned down, wants company a bit more for a while.
+    pub fn embarrassed() -> Self {
+        Self {
+            key: "embarrassed".into(),
+            time_left: 30.0,
+            decay: NeedDecay {
+                social: 1.5,
+                ..Default::default()
+            },
+            mood: -0.2,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.time_left <= 0.0
+    }
+}
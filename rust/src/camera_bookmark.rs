@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+//! Saved camera views, recalled with the number keys.
+//!
+use godot::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Where [crate::CameraRigOrbit] was looking from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub position: [f32; 3],
+    /// Radians around the vertical axis
+    pub yaw: f32,
+    /// Radians, negative looks down
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl CameraBookmark {
+    pub fn new(position: Vector3, yaw: f32, pitch: f32, distance: f32) -> Self {
+        Self {
+            position: [position.x, position.y, position.z],
+            yaw,
+            pitch,
+            distance,
+        }
+    }
+
+    pub fn position(&self) -> Vector3 {
+        let [x, y, z] = self.position;
+        Vector3::new(x, y, z)
+    }
+}
+
+/// Numbered bookmarks of one lot.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmarks {
+    slots: [Option<CameraBookmark>; Self::SLOTS],
+}
+
+impl CameraBookmarks {
+    /// Slots are numbered 1..=SLOTS, like the keys.
+    pub const SLOTS: usize = 9;
+
+    pub fn get(&self, slot: usize) -> Option<&CameraBookmark> {
+        self.slots.get(slot.checked_sub(1)?)?.as_ref()
+    }
+
+    /// Does nothing for slots out of range.
+    pub fn set(&mut self, slot: usize, bookmark: CameraBookmark) {
+        if let Some(entry) = slot
+            .checked_sub(1)
+            .and_then(|index| self.slots.get_mut(index))
+        {
+            *entry = Some(bookmark);
+        }
+    }
+}
@@ -6,15 +6,22 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use godot::classes::Engine;
 use godot::classes::{input::MouseMode, node::ProcessMode, InputEvent, InputEventMouseMotion};
 use godot::global::deg_to_rad;
 use godot::prelude::*;
 
-use crate::CameraCursorGizmo;
+use crate::{CameraBookmark, CameraCursorGizmo};
 
 const MIN_DISTANCE: f32 = 5.0;
 const MAX_DISTANCE: f32 = 100.0;
+/// The rig stays above the 32x32 lot.
+const LOT_MIN: Vector3 = Vector3::ZERO;
+const LOT_MAX: Vector3 = Vector3::new(32.0, 32.0, 32.0);
+/// How quickly the rig glides to a focus point. Higher is snappier.
+const FOCUS_SPEED: f32 = 6.0;
+/// Pitch limits in degrees, from straight down to nearly level.
+const MIN_PITCH: f32 = -90.0;
+const MAX_PITCH: f32 = -2.0;
 
 /// A multipurpose orbiting camera controller
 #[derive(Debug, GodotClass)]
@@ -27,6 +34,13 @@ pub struct CameraRigOrbit {
     cursor_gizmo: Gd<CameraCursorGizmo>,
 
     distance: f32,
+    /// Where the rig is gliding to, if anywhere
+    focus_target: Option<Vector3>,
+    /// What to keep in view while following
+    follow_target: Option<Gd<Node3D>>,
+    follow_enabled: bool,
+    /// Angles and distance being eased into, position goes through `focus_target`
+    view_target: Option<CameraBookmark>,
     stashed_mouse_pos: Option<Vector2>,
     mouse_delta: Vector2,
 
@@ -44,6 +58,10 @@ impl INode3D for CameraRigOrbit {
             cursor_gizmo: CameraCursorGizmo::new_alloc(),
 
             distance: 10.0,
+            focus_target: None,
+            follow_target: None,
+            follow_enabled: false,
+            view_target: None,
             stashed_mouse_pos: None,
             mouse_delta: Vector2::ZERO,
 
@@ -64,9 +82,8 @@ impl INode3D for CameraRigOrbit {
     }
 
     fn process(&mut self, delta: f64) {
-        let delta = delta / Engine::singleton().get_time_scale();
-
         self.process_input(delta);
+        self.process_focus(delta);
 
         self.camera.set_position(Vector3::BACK * self.distance);
     }
@@ -112,9 +129,15 @@ impl CameraRigOrbit {
             CamAction::Rotate
         };
 
+        if input.is_action_just_pressed("camera_toggle_follow") {
+            self.follow_enabled = !self.follow_enabled;
+        }
+
         if input.is_action_just_pressed("camera_zoom_in") {
+            self.view_target = None;
             self.zoom_in();
         } else if input.is_action_just_pressed("camera_zoom_out") {
+            self.view_target = None;
             self.zoom_out();
         }
 
@@ -136,12 +159,13 @@ impl CameraRigOrbit {
         }
 
         if action == CamAction::Rotate {
+            self.view_target = None;
             let vec = self.mouse_delta;
             self.base_mut().rotate_y(vec.x * -0.005);
 
             let mut pivot_rot = self.pivot.get_rotation_degrees();
             pivot_rot.x += vec.y * -0.2;
-            pivot_rot.x = pivot_rot.x.clamp(-90.0, -2.0);
+            pivot_rot.x = pivot_rot.x.clamp(MIN_PITCH, MAX_PITCH);
             self.pivot.set_rotation_degrees(pivot_rot);
         }
 
@@ -168,17 +192,114 @@ impl CameraRigOrbit {
             )
         };
 
+        // Taking the wheel stops any gliding
+        if move_vec != Vector3::ZERO {
+            self.focus_target = None;
+            self.view_target = None;
+            self.follow_enabled = false;
+        }
+
         let mut delta_move = move_vec * delta as f32 * self.distance;
         delta_move = delta_move.rotated(Vector3::UP, self.base().get_rotation().y);
 
         let mut pos = self.base().get_position();
         pos += delta_move;
-        pos = pos.clamp(Vector3::ZERO, Vector3::ONE * 32.0);
+        pos = pos.clamp(LOT_MIN, LOT_MAX);
         self.base_mut().set_position(pos);
 
         self.mouse_delta = Vector2::ZERO;
     }
 
+    /// Glide towards the focus point, or the followed target.
+    fn process_focus(&mut self, delta: f64) {
+        let weight = 1.0 - (-FOCUS_SPEED * delta as f32).exp();
+        self.process_view(weight);
+
+        if self.follow_enabled {
+            self.follow_target = self.follow_target.take().filter(|t| t.is_instance_valid());
+            if let Some(target) = &self.follow_target {
+                self.focus_target = Some(target.get_global_position());
+            }
+        }
+        let Some(target) = self.focus_target else {
+            return;
+        };
+
+        let target = target.clamp(LOT_MIN, LOT_MAX);
+        let pos = self.base().get_position();
+        let pos = pos.lerp(target, weight);
+        self.base_mut().set_position(pos);
+
+        if !self.follow_enabled && pos.distance_to(target) < 0.01 {
+            self.focus_target = None;
+        }
+    }
+
+    /// Ease yaw, pitch and distance towards a recalled bookmark.
+    fn process_view(&mut self, weight: f32) {
+        let Some(view) = self.view_target else {
+            return;
+        };
+
+        let yaw = self.base().get_rotation().y.lerp_angle(view.yaw, weight);
+        let mut rotation = self.base().get_rotation();
+        rotation.y = yaw;
+        self.base_mut().set_rotation(rotation);
+
+        let mut pivot_rot = self.pivot.get_rotation();
+        pivot_rot.x = pivot_rot.x.lerp(view.pitch, weight);
+        self.pivot.set_rotation(pivot_rot);
+
+        self.distance = self.distance.lerp(view.distance, weight);
+
+        let yaw_left = (yaw - view.yaw).sin().abs();
+        let pitch_left = (pivot_rot.x - view.pitch).abs();
+        if yaw_left < 0.001 && pitch_left < 0.001 && (self.distance - view.distance).abs() < 0.01 {
+            self.view_target = None;
+        }
+    }
+
+    /// The current view, to recall later with [Self::go_to_bookmark].
+    pub fn bookmark(&self) -> CameraBookmark {
+        CameraBookmark::new(
+            self.base().get_position(),
+            self.base().get_rotation().y,
+            self.pivot.get_rotation().x,
+            self.distance,
+        )
+    }
+
+    /// Glide over to a saved view. Stops following.
+    pub fn go_to_bookmark(&mut self, bookmark: &CameraBookmark) {
+        let mut view = *bookmark;
+        view.pitch = view
+            .pitch
+            .clamp(MIN_PITCH.to_radians(), MAX_PITCH.to_radians());
+        view.distance = view.distance.clamp(MIN_DISTANCE, MAX_DISTANCE);
+
+        self.follow_enabled = false;
+        self.focus_target = Some(view.position());
+        self.view_target = Some(view);
+    }
+
+    /// Glide over to look at `position`.
+    pub fn focus_on(&mut self, position: Vector3) {
+        self.focus_target = Some(position);
+    }
+
+    /// What follow mode keeps in view. Doesn't turn follow mode on.
+    pub fn set_follow_target(&mut self, target: Option<Gd<Node3D>>) {
+        self.follow_target = target;
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.follow_enabled
+    }
+
+    pub fn set_following(&mut self, follow: bool) {
+        self.follow_enabled = follow;
+    }
+
     fn zoom_in(&mut self) {
         self.distance *= 1.0 / 1.2;
         self.distance = self.distance.clamp(MIN_DISTANCE, MAX_DISTANCE);
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+
+/// Starting point, before anything nice or nasty is around.
+const BASE: f64 = 0.6;
+/// Nice things only help so much.
+const MAX_DECOR: f64 = 0.3;
+/// How much a pitch black spot takes off.
+const DARKNESS_PENALTY: f64 = 0.5;
+/// Rooms smaller than this, in tiles, feel cramped.
+const CRAMPED_SIZE: usize = 9;
+const CRAMPED_PENALTY: f64 = 0.2;
+/// Rooms at least this big, in tiles, feel roomy.
+const ROOMY_SIZE: usize = 30;
+const ROOMY_BONUS: f64 = 0.05;
+/// Fresh air, on a nice day.
+const OUTDOOR_BONUS: f64 = 0.1;
+
+/// What makes a spot pleasant to be in, piece by piece. See [crate::World::environment_score_of].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EnvironmentScore {
+    /// Positive decor of furniture around
+    pub decor: f64,
+    /// Negative decor around, like puddles. Zero or less
+    pub cleanliness: f64,
+    /// Zero or less, from how dark it is
+    pub lighting: f64,
+    /// Indoors, from room size
+    pub space: f64,
+    /// Outdoors, from how nice it is out
+    pub outdoors: f64,
+}
+
+impl EnvironmentScore {
+    /// `decor` is the decor of each piece of furniture around, `brightness` and `outdoor_comfort`
+    /// are 0.0..=1.0, `room_size` is None outdoors.
+    pub fn new(
+        decor: impl IntoIterator<Item = f64>,
+        brightness: f64,
+        room_size: Option<usize>,
+        outdoor_comfort: f64,
+    ) -> Self {
+        let (nice, nasty): (Vec<f64>, Vec<f64>) = decor.into_iter().partition(|d| *d >= 0.0);
+
+        let space = match room_size {
+            Some(size) if size < CRAMPED_SIZE => {
+                -CRAMPED_PENALTY * (1.0 - size as f64 / CRAMPED_SIZE as f64)
+            }
+            Some(size) if size >= ROOMY_SIZE => ROOMY_BONUS,
+            _ => 0.0,
+        };
+        let outdoors = match room_size {
+            Some(_) => 0.0,
+            None => OUTDOOR_BONUS * outdoor_comfort,
+        };
+
+        Self {
+            decor: nice.iter().sum::<f64>().min(MAX_DECOR),
+            cleanliness: nasty.iter().sum(),
+            lighting: -(1.0 - brightness.clamp(0.0, 1.0)) * DARKNESS_PENALTY,
+            space,
+            outdoors,
+        }
+    }
+
+    /// 0.0..=1.0, what the environment need heads towards.
+    pub fn total(&self) -> f64 {
+        (BASE + self.decor + self.cleanliness + self.lighting + self.space + self.outdoors)
+            .clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+//! Things that happen at set game times, like bills or visitors.
+//!
+//! Times are game minutes since midnight of day 0, see [crate::GameClock::total_minutes].
+//! No Godot types in here.
+//!
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::Weekday;
+
+const MINUTES_PER_DAY: f64 = 60.0 * 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recurrence {
+    Once,
+    Daily,
+    Weekly,
+}
+
+impl Recurrence {
+    /// Minutes between occurrences, none for one-shot events.
+    pub fn interval(self) -> Option<f64> {
+        match self {
+            Recurrence::Once => None,
+            Recurrence::Daily => Some(MINUTES_PER_DAY),
+            Recurrence::Weekly => Some(MINUTES_PER_DAY * 7.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    pub id: u64,
+    /// What happens, handled by whoever owns the scheduler. E.g. "bills"
+    pub key: String,
+    /// Next occurrence
+    pub at: f64,
+    pub recurrence: Recurrence,
+    /// Who it's about, if anyone. Missing from older saves
+    #[serde(default)]
+    pub subject: Option<Uuid>,
+}
+
+/// One occurrence of an event that came due.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiredEvent {
+    pub id: u64,
+    pub key: String,
+    /// When it was meant to happen, may be before now after a big time step
+    pub at: f64,
+    pub subject: Option<Uuid>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventScheduler {
+    next_id: u64,
+    events: Vec<ScheduledEvent>,
+}
+
+impl EventScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule `key` at `at` minutes. Returns an id for [Self::cancel].
+    pub fn schedule_at(&mut self, key: &str, at: f64, recurrence: Recurrence) -> u64 {
+        self.push(key, at, recurrence, None)
+    }
+
+    /// Schedule `key` once at `at` minutes, about `subject`. E.g. a visitor leaving.
+    pub fn schedule_for(&mut self, key: &str, at: f64, subject: Uuid) -> u64 {
+        self.push(key, at, Recurrence::Once, Some(subject))
+    }
+
+    fn push(&mut self, key: &str, at: f64, recurrence: Recurrence, subject: Option<Uuid>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.events.push(ScheduledEvent {
+            id,
+            key: key.into(),
+            at,
+            recurrence,
+            subject,
+        });
+        id
+    }
+
+    /// Every day at `hour:minute`, starting from the first one not before `now`.
+    pub fn schedule_daily(&mut self, key: &str, now: f64, hour: u32, minute: u32) -> u64 {
+        let day_start = (now / MINUTES_PER_DAY).floor() * MINUTES_PER_DAY;
+        let mut at = day_start + (hour * 60 + minute) as f64;
+        if at < now {
+            at += MINUTES_PER_DAY;
+        }
+        self.schedule_at(key, at, Recurrence::Daily)
+    }
+
+    /// Every week on `weekday` at `hour:minute`, starting from the first one not before `now`.
+    pub fn schedule_weekly(
+        &mut self,
+        key: &str,
+        now: f64,
+        weekday: Weekday,
+        hour: u32,
+        minute: u32,
+    ) -> u64 {
+        let week = MINUTES_PER_DAY * 7.0;
+        let week_start = (now / week).floor() * week;
+        let day = Weekday::ALL.iter().position(|d| *d == weekday).unwrap_or(0) as f64;
+        let mut at = week_start + day * MINUTES_PER_DAY + (hour * 60 + minute) as f64;
+        if at < now {
+            at += week;
+        }
+        self.schedule_at(key, at, Recurrence::Weekly)
+    }
+
+    /// Returns false if there was no such event.
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let count = self.events.len();
+        self.events.retain(|e| e.id != id);
+        self.events.len() != count
+    }
+
+    pub fn events(&self) -> &[ScheduledEvent] {
+        &self.events
+    }
+
+    /// The soonest event, if any.
+    pub fn next_event(&self) -> Option<&ScheduledEvent> {
+        self.next_index().map(|i| &self.events[i])
+    }
+
+    /// Ties go to the event scheduled first, so order doesn't depend on storage.
+    fn next_index(&self) -> Option<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.at.total_cmp(&b.at).then(a.id.cmp(&b.id)))
+            .map(|(i, _)| i)
+    }
+
+    /// Everything due at or before `now`, oldest first. Recurring events that were skipped over
+    /// by a big time step fire once per missed occurrence, then get rescheduled past `now`.
+    pub fn take_due(&mut self, now: f64) -> Vec<FiredEvent> {
+        let mut fired = vec![];
+        while let Some(index) = self.next_index().filter(|i| self.events[*i].at <= now) {
+            let event = &mut self.events[index];
+            fired.push(FiredEvent {
+                id: event.id,
+                key: event.key.clone(),
+                at: event.at,
+                subject: event.subject,
+            });
+
+            match event.recurrence.interval() {
+                Some(interval) => event.at += interval,
+                None => {
+                    self.events.remove(index);
+                }
+            }
+        }
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: f64 = 60.0;
+
+    fn keys(fired: &[FiredEvent]) -> Vec<&str> {
+        fired.iter().map(|e| e.key.as_str()).collect()
+    }
+
+    #[test]
+    fn nothing_due_before_time() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule_at("party", 10.0 * HOUR, Recurrence::Once);
+
+        assert!(scheduler.take_due(9.0 * HOUR).is_empty());
+        assert_eq!(keys(&scheduler.take_due(10.0 * HOUR)), vec!["party"]);
+        assert!(scheduler.events().is_empty());
+    }
+
+    #[test]
+    fn big_step_fires_everything_in_order() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule_at("late", 12.0 * HOUR, Recurrence::Once);
+        scheduler.schedule_at("early", 9.0 * HOUR, Recurrence::Once);
+        scheduler.schedule_daily("carpool", 0.0, 8, 0);
+
+        // Three days at once
+        let fired = scheduler.take_due(3.0 * MINUTES_PER_DAY);
+        assert_eq!(
+            keys(&fired),
+            vec!["carpool", "early", "late", "carpool", "carpool"]
+        );
+        let times: Vec<f64> = fired.iter().map(|e| e.at).collect();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn daily_reschedules_for_tomorrow() {
+        let mut scheduler = EventScheduler::new();
+        // Past 8:00 already, so the first one is tomorrow
+        let id = scheduler.schedule_daily("carpool", 9.0 * HOUR, 8, 0);
+        assert_eq!(
+            scheduler.next_event().map(|e| e.at),
+            Some(MINUTES_PER_DAY + 8.0 * HOUR)
+        );
+
+        let fired = scheduler.take_due(MINUTES_PER_DAY + 8.0 * HOUR);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].id, id);
+        assert_eq!(
+            scheduler.next_event().map(|e| e.at),
+            Some(2.0 * MINUTES_PER_DAY + 8.0 * HOUR)
+        );
+    }
+
+    #[test]
+    fn weekly_reschedules_for_next_week() {
+        let week = MINUTES_PER_DAY * 7.0;
+        let mut scheduler = EventScheduler::new();
+        // Day 0 is a Monday, start on Wednesday
+        scheduler.schedule_weekly("bills", 2.0 * MINUTES_PER_DAY, Weekday::Monday, 9, 0);
+        let first = week + 9.0 * HOUR;
+        assert_eq!(scheduler.next_event().map(|e| e.at), Some(first));
+
+        assert_eq!(scheduler.take_due(first).len(), 1);
+        assert_eq!(scheduler.next_event().map(|e| e.at), Some(first + week));
+
+        // Skipping three weeks fires the missed ones
+        assert_eq!(scheduler.take_due(first + 3.5 * week).len(), 3);
+    }
+
+    #[test]
+    fn fired_events_keep_their_subject() {
+        let mut scheduler = EventScheduler::new();
+        let visitor = Uuid::new_v4();
+        scheduler.schedule_for("visitor_leaves", HOUR, visitor);
+
+        let fired = scheduler.take_due(HOUR);
+        assert_eq!(fired[0].subject, Some(visitor));
+        assert!(scheduler.events().is_empty());
+    }
+
+    #[test]
+    fn cancelled_events_dont_fire() {
+        let mut scheduler = EventScheduler::new();
+        let id = scheduler.schedule_daily("carpool", 0.0, 8, 0);
+
+        assert!(scheduler.cancel(id));
+        assert!(!scheduler.cancel(id));
+        assert!(scheduler.take_due(MINUTES_PER_DAY * 2.0).is_empty());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule_daily("carpool", 0.0, 8, 0);
+        scheduler.schedule_weekly("bills", 0.0, Weekday::Monday, 9, 0);
+        scheduler.schedule_at("party", 100.0, Recurrence::Once);
+
+        let json = serde_json::to_string(&scheduler).unwrap();
+        let mut loaded: EventScheduler = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, scheduler);
+
+        // Ids keep counting up after loading
+        let id = loaded.schedule_at("later", 200.0, Recurrence::Once);
+        assert_eq!(id, 3);
+    }
+}
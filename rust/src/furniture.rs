@@ -1,16 +1,88 @@
 // SPDX-License-Identifier: LGPL-3.0-or-later
 use godot::{
-    classes::{BoxShape3D, Shape3D},
+    classes::{light_3d::Param as LightParam, BoxShape3D, OmniLight3D, Shape3D},
     prelude::*,
 };
 
-use crate::{ActionAdvertisement, EntityCollider, Person};
+use crate::{
+    ActionAdvertisement, ActionAdvertisementSource, ActionAdvertisementStat, EntityCollider, Person,
+};
+
+/// Light fixtures that can be placed as furniture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LampKind {
+    Ceiling,
+    Floor,
+    /// Comes with its own little nightstand
+    Table,
+}
+
+impl LampKind {
+    pub fn model_path(self) -> &'static str {
+        match self {
+            LampKind::Ceiling => "res://assets/prefabs/mdl_lamp_ceiling.tscn",
+            LampKind::Floor => "res://assets/prefabs/mdl_lamp_floor.tscn",
+            LampKind::Table => "res://assets/prefabs/mdl_lamp_table.tscn",
+        }
+    }
+
+    /// Size of the clickable box.
+    pub fn collider_size(self) -> Vector3 {
+        match self {
+            LampKind::Ceiling => Vector3::new(0.5, 0.25, 0.5),
+            LampKind::Floor => Vector3::new(0.5, 1.7, 0.5),
+            LampKind::Table => Vector3::new(0.5, 0.8, 0.5),
+        }
+    }
+
+    pub fn collider_offset(self) -> Vector3 {
+        let size = self.collider_size();
+        match self {
+            LampKind::Ceiling => Vector3::new(0.0, 2.0 - size.y / 2.0, 0.0),
+            LampKind::Floor => Vector3::UP * size.y / 2.0,
+            LampKind::Table => size / 2.0,
+        }
+    }
+
+    /// Where the bulb is, relative to the lamp.
+    pub fn light_offset(self) -> Vector3 {
+        match self {
+            LampKind::Ceiling => Vector3::new(0.0, 1.8, 0.0),
+            LampKind::Floor => Vector3::new(0.0, 1.5, 0.0),
+            LampKind::Table => Vector3::new(0.25, 0.75, 0.25),
+        }
+    }
+
+    /// How far the light reaches, in meters.
+    pub fn light_range(self) -> f32 {
+        match self {
+            LampKind::Ceiling => 6.0,
+            LampKind::Floor => 4.0,
+            LampKind::Table => 2.5,
+        }
+    }
+
+    /// 0.0..=1.0, how bright it is up close.
+    pub fn light_energy(self) -> f32 {
+        match self {
+            LampKind::Ceiling => 1.0,
+            LampKind::Floor => 0.8,
+            LampKind::Table => 0.6,
+        }
+    }
+}
 
 #[derive(Debug, GodotClass)]
 #[class(base=Node3D)]
 pub struct Furniture {
     actions: Vec<ActionAdvertisement>,
     reserved_by: Option<Gd<Person>>,
+    /// How this affects the environment of people nearby. Negative is bad.
+    decor: f64,
+    /// Environment score where this stands, kept up to date by [crate::World]
+    environment: f64,
+    /// Lamps only
+    light: Option<Gd<OmniLight3D>>,
 
     node_visuals: Gd<Node3D>,
     node_collider: Gd<EntityCollider>,
@@ -32,6 +104,9 @@ impl INode3D for Furniture {
         Self {
             actions: vec![],
             reserved_by: None,
+            decor: 0.0,
+            environment: 1.0,
+            light: None,
 
             node_visuals: Self::build_visuals("res://assets/models/mdl_debug_error.glb"),
             node_collider: Self::build_collider(coll_shape, coll_offset),
@@ -77,6 +152,9 @@ impl Furniture {
         Gd::from_init_fn(|base| Self {
             actions,
             reserved_by: None,
+            decor: 0.0,
+            environment: 1.0,
+            light: None,
 
             node_visuals: Self::build_visuals(model_path),
             node_collider: Self::build_collider(coll_shape, coll_offset),
@@ -85,6 +163,35 @@ impl Furniture {
         })
     }
 
+    /// A lamp, switched off.
+    pub fn new_lamp(kind: LampKind) -> Gd<Self> {
+        let mut coll_box = BoxShape3D::new_gd();
+        coll_box.set_size(kind.collider_size());
+
+        let mut lamp = Self::new(
+            kind.model_path(),
+            coll_box.upcast::<Shape3D>(),
+            kind.collider_offset(),
+            vec![],
+        );
+
+        let mut light = OmniLight3D::new_alloc();
+        light.set_name("light");
+        light.set_position(kind.light_offset());
+        light.set_param(LightParam::RANGE, kind.light_range());
+        light.set_param(LightParam::ENERGY, kind.light_energy());
+        light.set_color(Color::from_rgb(1.0, 0.85, 0.65));
+        lamp.add_child(&light);
+
+        {
+            let mut lamp = lamp.bind_mut();
+            lamp.light = Some(light);
+            lamp.set_decor(0.05);
+            lamp.set_lit(false);
+        }
+        lamp
+    }
+
     fn build_visuals(model_path: &str) -> Gd<Node3D> {
         let model_packed: Gd<PackedScene> = load(model_path);
         let mut model = model_packed.instantiate().unwrap();
@@ -144,4 +251,65 @@ impl Furniture {
     pub fn unreserve(&mut self) {
         self.reserved_by = None
     }
+
+    pub fn is_lamp(&self) -> bool {
+        self.light.is_some()
+    }
+
+    pub fn is_lit(&self) -> bool {
+        self.light.as_ref().is_some_and(|l| l.is_visible())
+    }
+
+    /// Switch a lamp. Also swaps the switch action on offer.
+    pub fn set_lit(&mut self, lit: bool) {
+        let Some(light) = &mut self.light else {
+            return;
+        };
+        light.set_visible(lit);
+
+        let (action_key, value) = if lit {
+            ("light_off", 1)
+        } else {
+            ("light_on", 4)
+        };
+        let this = self.to_gd();
+        self.actions
+            .retain(|a| a.action_key != "light_on" && a.action_key != "light_off");
+        self.actions.push(ActionAdvertisement {
+            action_key: action_key.into(),
+            source: ActionAdvertisementSource::Furniture(this),
+            stats: vec![ActionAdvertisementStat {
+                key: "environment".into(),
+                value,
+            }],
+            required_people: 1,
+        });
+    }
+
+    /// 0.0..=1.0, how much this lights up `position`. Walls aren't considered.
+    pub fn brightness_at(&self, position: Vector3) -> f64 {
+        let Some(light) = self.light.as_ref().filter(|l| l.is_visible()) else {
+            return 0.0;
+        };
+        let range = light.get_param(LightParam::RANGE);
+        let energy = light.get_param(LightParam::ENERGY);
+        let distance = light.get_global_position().distance_to(position);
+        (energy * (1.0 - distance / range)).max(0.0) as f64
+    }
+
+    pub fn environment(&self) -> f64 {
+        self.environment
+    }
+
+    pub fn set_environment(&mut self, environment: f64) {
+        self.environment = environment
+    }
+
+    pub fn decor(&self) -> f64 {
+        self.decor
+    }
+
+    pub fn set_decor(&mut self, decor: f64) {
+        self.decor = decor
+    }
 }
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use uuid::Uuid;
+
+use crate::save_data::HouseholdSave;
+
+/// A family of [crate::Person]s living together, usually on a lot. Only the active household is
+/// playable.
+#[derive(Debug, Clone)]
+pub struct Household {
+    uuid: Uuid,
+    pub name: String,
+    /// In the order they're cycled through.
+    members: Vec<Uuid>,
+    /// Shared money, in whole currency units
+    pub funds: i64,
+    /// Name of the lot they live on, None if they don't have one
+    pub lot: Option<String>,
+}
+
+impl Household {
+    pub const STARTING_FUNDS: i64 = 20_000;
+
+    pub fn new(name: &str, lot: Option<&str>) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            name: name.into(),
+            members: vec![],
+            funds: Self::STARTING_FUNDS,
+            lot: lot.map(Into::into),
+        }
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn members(&self) -> &[Uuid] {
+        &self.members
+    }
+
+    pub fn has_member(&self, uuid: &Uuid) -> bool {
+        self.members.contains(uuid)
+    }
+
+    pub fn add_member(&mut self, uuid: Uuid) {
+        if !self.has_member(&uuid) {
+            self.members.push(uuid);
+        }
+    }
+
+    pub fn set_members(&mut self, members: Vec<Uuid>) {
+        self.members = members;
+    }
+
+    /// Reorder a member. `index` is clamped to the end.
+    pub fn move_member(&mut self, uuid: &Uuid, index: usize) {
+        let Some(from) = self.members.iter().position(|m| m == uuid) else {
+            return;
+        };
+        let member = self.members.remove(from);
+        let index = index.min(self.members.len());
+        self.members.insert(index, member);
+    }
+
+    pub fn remove_member(&mut self, uuid: &Uuid) {
+        self.members.retain(|m| m != uuid);
+    }
+
+    /// Member after `current`, wrapping around. First member if `current` isn't one.
+    pub fn next_member(&self, current: Option<Uuid>) -> Option<Uuid> {
+        let index = current
+            .and_then(|uuid| self.members.iter().position(|m| *m == uuid))
+            .map_or(0, |i| (i + 1) % self.members.len());
+        self.members.get(index).copied()
+    }
+
+    /// Only those with a lot have bills to pay. Returns whether they paid.
+    pub fn pay_bills(&mut self, amount: i64) -> bool {
+        if self.lot.is_none() {
+            return false;
+        }
+        self.funds -= amount;
+        true
+    }
+
+    pub fn to_save(&self) -> HouseholdSave {
+        HouseholdSave {
+            name: self.name.clone(),
+            members: self.members.clone(),
+            funds: self.funds,
+            lot: self.lot.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bills_come_with_a_lot() {
+        let mut household = Household::new("Smith", Some("Sunny Acres"));
+        assert!(household.pay_bills(250));
+        assert_eq!(household.funds, Household::STARTING_FUNDS - 250);
+    }
+
+    #[test]
+    fn no_lot_no_bills() {
+        let mut household = Household::new("Jones", None);
+        assert!(!household.pay_bills(250));
+        assert_eq!(household.funds, Household::STARTING_FUNDS);
+    }
+}
@@ -1,42 +1,71 @@
 // SPDX-License-Identifier: LGPL-3.0-or-later
 mod action;
 mod action_advertisement;
+mod autonomy;
+mod buff;
+mod camera_bookmark;
 mod camera_cursor_gizmo;
 mod camera_rig_orbit;
 mod entity_collider;
+mod environment_score;
+mod event_scheduler;
 mod furniture;
+mod household;
 pub mod lot_builder;
 pub mod lot_data;
 mod person;
 mod person_ai;
 mod person_needs;
+mod person_profile;
+mod relationship;
+pub mod save_data;
 mod spirit_level;
 mod time;
+mod ui_action_queue;
+mod ui_character_creator;
 mod ui_debug_ovl;
 mod ui_person_bio_panel;
 mod ui_person_needs_panel;
+mod ui_person_relationships_panel;
+mod ui_pie_menu;
 mod ui_world_mode_select;
 mod ui_world_taskbar;
+mod weather;
 mod world;
 mod world_env;
 mod world_view_mode;
 
-pub use action::Action;
-pub use action_advertisement::{ActionAdvertisement, ActionAdvertisementStat};
+pub use action::{Action, ActionPriority, GroupRole};
+pub use action_advertisement::{
+    ActionAdvertisement, ActionAdvertisementSource, ActionAdvertisementStat,
+};
+pub use autonomy::Autonomy;
+pub use buff::Buff;
+pub use camera_bookmark::{CameraBookmark, CameraBookmarks};
 pub use camera_cursor_gizmo::CameraCursorGizmo;
 pub use camera_rig_orbit::CameraRigOrbit;
 pub use entity_collider::EntityCollider;
-pub use furniture::Furniture;
+pub use environment_score::EnvironmentScore;
+pub use event_scheduler::{EventScheduler, FiredEvent, Recurrence, ScheduledEvent};
+pub use furniture::{Furniture, LampKind};
+pub use household::Household;
 pub use person::{Person, Task};
-pub use person_ai::PersonAi;
-pub use person_needs::PersonNeeds;
+pub use person_ai::{PersonAi, ProposalResponse};
+pub use person_needs::{NeedDecay, PersonNeeds};
+pub use person_profile::{AgeStage, PersonProfile, PersonTrait};
+pub use relationship::{Relationship, RelationshipLabel};
 pub use spirit_level::SpiritLevel;
-pub use time::TimeScale;
+pub use time::{ClockTick, GameClock, Season, TimeScale, WakeReason, Weekday};
+pub use ui_action_queue::UiActionQueue;
+pub use ui_character_creator::UiCharacterCreator;
 pub use ui_debug_ovl::UiDebugOvl;
 pub use ui_person_bio_panel::UiPersonBioPanel;
 pub use ui_person_needs_panel::UiPersonNeedsPanel;
+pub use ui_person_relationships_panel::UiPersonRelationshipsPanel;
+pub use ui_pie_menu::{UiPieMenu, UiPieMenuEntry};
 pub use ui_world_mode_select::UiWorldModeSelectOld;
 pub use ui_world_taskbar::UiWorldTaskbar;
+pub use weather::Weather;
 pub use world::World;
 pub use world_env::WorldEnv;
 pub use world_view_mode::WorldViewMode;
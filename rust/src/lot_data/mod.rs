@@ -5,6 +5,8 @@
 //! No Godot Nodes within this module.
 //!
 
+mod rooms;
 mod walls;
 
+pub use rooms::RoomMap;
 pub use walls::{Wall, Walls};
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use std::collections::VecDeque;
+
+use godot::prelude::*;
+
+use super::Walls;
+
+/// Tiles per side of the lot. The wall grid is one bigger.
+const LOT_TILES: i32 = 32;
+
+/// Which tiles are closed off by walls, and into which rooms.
+///
+/// Tile `(x, y)` covers the square between wall grid points `(x, y)` and `(x + 1, y + 1)`.
+/// Anything connected to the lot edge without passing a wall is outdoors.
+#[derive(Debug, Clone)]
+pub struct RoomMap {
+    /// Room index per tile, row by row. None is outdoors
+    tiles: Vec<Option<usize>>,
+    /// Tile count of each room
+    room_sizes: Vec<usize>,
+    /// Middle of each room's floor
+    room_centers: Vec<Vector3>,
+}
+
+impl Default for RoomMap {
+    fn default() -> Self {
+        Self {
+            tiles: vec![None; (LOT_TILES * LOT_TILES) as usize],
+            room_sizes: vec![],
+            room_centers: vec![],
+        }
+    }
+}
+
+impl RoomMap {
+    pub fn from_walls(walls: &Walls) -> Self {
+        let mut map = Self::default();
+        let mut visited = vec![false; map.tiles.len()];
+
+        for start in 0..map.tiles.len() {
+            if visited[start] {
+                continue;
+            }
+
+            // Flood fill until walls, noting whether the lot edge was reached
+            let mut region = vec![];
+            let mut outdoors = false;
+            let mut queue = VecDeque::from([start]);
+            visited[start] = true;
+            while let Some(index) = queue.pop_front() {
+                region.push(index);
+                let tile = Self::index_to_tile(index);
+                for (neighbor, wall) in Self::neighbors(tile) {
+                    if walls.has_wall(wall) {
+                        continue;
+                    }
+                    let Some(neighbor_index) = Self::tile_to_index(neighbor) else {
+                        outdoors = true;
+                        continue;
+                    };
+                    if !visited[neighbor_index] {
+                        visited[neighbor_index] = true;
+                        queue.push_back(neighbor_index);
+                    }
+                }
+            }
+
+            if !outdoors {
+                let room = map.room_sizes.len();
+                map.room_sizes.push(region.len());
+                let mut center = Vector3::ZERO;
+                for &index in &region {
+                    map.tiles[index] = Some(room);
+                    let tile = Self::index_to_tile(index);
+                    center += Vector3::new(tile.x as f32 + 0.5, 0.0, tile.y as f32 + 0.5);
+                }
+                map.room_centers.push(center / region.len() as f32);
+            }
+        }
+
+        map
+    }
+
+    /// Room at a world position, None if outdoors or off the lot.
+    pub fn room_at(&self, position: Vector3) -> Option<usize> {
+        let tile = Vector2i::new(position.x.floor() as i32, position.z.floor() as i32);
+        self.tiles[Self::tile_to_index(tile)?]
+    }
+
+    /// Floor area of a room, in tiles.
+    pub fn room_size(&self, room: usize) -> usize {
+        self.room_sizes.get(room).copied().unwrap_or(0)
+    }
+
+    /// Middle of a room's floor, on the ground. Might be outside an oddly shaped room.
+    pub fn room_center(&self, room: usize) -> Option<Vector3> {
+        self.room_centers.get(room).copied()
+    }
+
+    pub fn room_count(&self) -> usize {
+        self.room_sizes.len()
+    }
+
+    fn tile_to_index(tile: Vector2i) -> Option<usize> {
+        let on_lot = (0..LOT_TILES).contains(&tile.x) && (0..LOT_TILES).contains(&tile.y);
+        on_lot.then_some((tile.y * LOT_TILES + tile.x) as usize)
+    }
+
+    fn index_to_tile(index: usize) -> Vector2i {
+        Vector2i::new(index as i32 % LOT_TILES, index as i32 / LOT_TILES)
+    }
+
+    /// Adjacent tiles, with the wall span that would separate them.
+    fn neighbors(tile: Vector2i) -> [(Vector2i, (Vector2i, Vector2i)); 4] {
+        let Vector2i { x, y } = tile;
+        [
+            (
+                Vector2i::new(x - 1, y),
+                (Vector2i::new(x, y), Vector2i::new(x, y + 1)),
+            ),
+            (
+                Vector2i::new(x + 1, y),
+                (Vector2i::new(x + 1, y), Vector2i::new(x + 1, y + 1)),
+            ),
+            (
+                Vector2i::new(x, y - 1),
+                (Vector2i::new(x, y), Vector2i::new(x + 1, y)),
+            ),
+            (
+                Vector2i::new(x, y + 1),
+                (Vector2i::new(x, y + 1), Vector2i::new(x + 1, y + 1)),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two by two tiles, from grid point (2, 2) to (4, 4).
+    fn small_room() -> Walls {
+        let span = |(ax, ay), (bx, by)| (Vector2i::new(ax, ay), Vector2i::new(bx, by));
+        Walls::from_spans([
+            span((2, 2), (3, 2)),
+            span((3, 2), (4, 2)),
+            span((2, 4), (3, 4)),
+            span((3, 4), (4, 4)),
+            span((2, 2), (2, 3)),
+            span((2, 3), (2, 4)),
+            span((4, 2), (4, 3)),
+            span((4, 3), (4, 4)),
+        ])
+    }
+
+    #[test]
+    fn walls_close_off_a_room() {
+        let map = RoomMap::from_walls(&small_room());
+
+        assert_eq!(map.room_count(), 1);
+        assert_eq!(map.room_size(0), 4);
+        assert_eq!(map.room_at(Vector3::new(2.5, 0.0, 3.5)), Some(0));
+        assert_eq!(map.room_at(Vector3::new(1.5, 0.0, 3.5)), None);
+        assert_eq!(map.room_center(0), Some(Vector3::new(3.0, 0.0, 3.0)));
+    }
+
+    #[test]
+    fn open_room_is_outdoors() {
+        let mut walls = small_room();
+        walls.remove_wall((Vector2i::new(2, 2), Vector2i::new(3, 2)));
+        let map = RoomMap::from_walls(&walls);
+
+        assert_eq!(map.room_count(), 0);
+        assert_eq!(map.room_at(Vector3::new(2.5, 0.0, 2.5)), None);
+        assert_eq!(map.room_center(0), None);
+    }
+}
@@ -72,6 +72,31 @@ impl Walls {
         Self { walls }
     }
 
+    /// Walls from their spans, like in a save. Invalid spans are skipped.
+    pub fn from_spans(spans: impl IntoIterator<Item = (Vector2i, Vector2i)>) -> Self {
+        let mut walls = Self {
+            walls: HashMap::new(),
+        };
+        for (start, end) in spans {
+            match Wall::new(start, end) {
+                Ok(wall) => walls.add_wall(wall),
+                Err(e) => godot_warn!("Skipped wall {start}-{end}: {e}"),
+            }
+        }
+        walls
+    }
+
+    /// Spans of every wall, in a stable order.
+    pub fn spans(&self) -> Vec<(Vector2i, Vector2i)> {
+        let mut spans: Vec<_> = self
+            .walls
+            .values()
+            .map(|w| Self::span_sorted(w.span()))
+            .collect();
+        spans.sort_by_key(|(a, b)| (a.x, a.y, b.x, b.y));
+        spans
+    }
+
     pub fn add_wall(&mut self, wall: Wall) {
         let k = Self::span_sorted(wall.span());
         self.walls.insert(k, wall);
@@ -82,6 +107,10 @@ impl Walls {
         self.walls.remove(&k);
     }
 
+    pub fn has_wall(&self, span: (Vector2i, Vector2i)) -> bool {
+        self.walls.contains_key(&Self::span_sorted(span))
+    }
+
     /// Smaller X first. If X are equal, smaller Y first.
     const fn span_sorted(span: (Vector2i, Vector2i)) -> (Vector2i, Vector2i) {
         if span.0.x < span.1.x {
@@ -2,12 +2,31 @@
 //! class: [Person]
 //! desc: The human entity
 //!
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use godot::prelude::*;
 use uuid::Uuid;
 
-use crate::{Action, EntityCollider, PersonAi, PersonNeeds, World};
+use godot::classes::{
+    base_material_3d::BillboardMode, CsgSphere3D, Label3D, MeshInstance3D, StandardMaterial3D,
+};
+
+use crate::{
+    save_data::PersonSave, Action, ActionAdvertisement, ActionAdvertisementSource,
+    ActionAdvertisementStat, ActionPriority, Autonomy, Buff, EntityCollider, GroupRole, NeedDecay,
+    PersonAi, PersonNeeds, PersonProfile, ProposalResponse, Relationship, World,
+};
+
+/// Needs below this may interrupt autonomous tasks.
+const URGENT_NEED_THRESHOLD: f64 = 0.15;
+/// Task ends when its primary need reaches this.
+const SATISFIED_NEED_THRESHOLD: f64 = 0.95;
+/// How far apart people stand when socializing.
+const SOCIAL_DISTANCE: f32 = 1.0;
+/// Give up on a partner who hasn't joined after this many seconds.
+const MAX_PARTNER_WAIT: f64 = 20.0;
+/// How far from the center people stand in a group.
+const GROUP_RADIUS: f32 = 1.2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskState {
@@ -18,26 +37,133 @@ pub enum TaskState {
     Done,
 }
 
+/// Why a task couldn't go on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskError {
+    /// Partner or group leader isn't in the world anymore.
+    PartnerMissing(Uuid),
+    /// Partner or group leader moved on before this person could join.
+    PartnerLeft(Uuid),
+    /// Partner or group leader never showed up.
+    PartnerTimeout(Uuid),
+    /// The furniture this was about got removed, like a cleaned up puddle.
+    ObjectMissing,
+    /// No idea how to perform this action.
+    UnknownAction(String),
+}
+
+impl std::fmt::Display for TaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskError::PartnerMissing(uuid) => write!(f, "partner `{uuid}` is gone"),
+            TaskError::PartnerLeft(uuid) => write!(f, "partner `{uuid}` left before it began"),
+            TaskError::PartnerTimeout(uuid) => write!(f, "partner `{uuid}` never showed up"),
+            TaskError::ObjectMissing => write!(f, "object is gone"),
+            TaskError::UnknownAction(key) => write!(f, "unknown action `{key}`"),
+        }
+    }
+}
+
+/// What to do after someone left a task this person is part of, see [company_left].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompanyLeft {
+    /// Not the current task, or enough of the group remains.
+    CarryOn,
+    /// End our copy of the task. The one who left already knows.
+    EndTask,
+    /// Leader lost quorum. End the task and let the remaining members know.
+    BreakUp,
+}
+
+/// `leaver` left task `task_uuid`, maybe along with the world. Queued copies of the task are
+/// forgotten, since they never started, and `task` is ended unless it carries on. Doesn't touch
+/// Godot, cleaning up after the ended task is up to the caller.
+fn company_left(
+    task: &mut Task,
+    queue: &mut VecDeque<Action>,
+    leaver: Uuid,
+    task_uuid: Uuid,
+) -> CompanyLeft {
+    queue.retain(|a| a.master_uuid != Some(task_uuid));
+    if task.uuid != task_uuid || task.state == TaskState::Done {
+        return CompanyLeft::CarryOn;
+    }
+
+    if let Some(GroupRole::Leader { members, .. }) = &mut task.action.group {
+        members.retain(|m| *m != leaver);
+    }
+    let left = match &task.action.group {
+        Some(group @ GroupRole::Leader { .. }) if !group.has_quorum() => CompanyLeft::BreakUp,
+        Some(GroupRole::Leader { .. }) => CompanyLeft::CarryOn,
+        _ => CompanyLeft::EndTask,
+    };
+    if left != CompanyLeft::CarryOn {
+        task.end();
+    }
+    left
+}
+
+/// Who to tell, and about which task, when this person drops `task` and everything in `queue`,
+/// like when leaving the world. Queued actions only matter if someone else started them.
+fn leaving_notices(task: &Task, queue: &VecDeque<Action>) -> Vec<(Uuid, Uuid)> {
+    let mut notices = vec![];
+    if task.state != TaskState::Done {
+        notices.extend(
+            task.action
+                .company()
+                .into_iter()
+                .map(|uuid| (uuid, task.uuid)),
+        );
+    }
+    for action in queue {
+        if let Some(master_uuid) = action.master_uuid {
+            notices.extend(action.company().into_iter().map(|uuid| (uuid, master_uuid)));
+        }
+    }
+    notices
+}
+
+/// Lasting effect of a task that ran its course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOutcome {
+    Nothing,
+    WellRested,
+    /// The object is used up, like a cleaned up puddle.
+    RemoveObject,
+    SwitchLamp {
+        lit: bool,
+    },
+}
+
 #[derive(Debug)]
 pub struct Task {
     uuid: Uuid,
     state: TaskState,
     action: Action,
     target_position: Option<Vector3>,
-    time_left: f64,
+    /// Seconds spent in progress
+    elapsed: f64,
+    /// Seconds spent waiting for a partner to join
+    waited: f64,
+    /// Can an urgent need interrupt this task, as far as the task goes. Whether the person's
+    /// autonomy allows it is checked as it happens, see [Person::check_urgent_needs].
+    preemptible: bool,
 }
 
 impl Task {
     pub fn new(action: Action) -> Self {
         let target_position = action.object.as_ref().map(|f| f.get_global_position());
         let uuid = action.master_uuid.unwrap_or(Uuid::new_v4());
+        let preemptible = action.priority != ActionPriority::Forced;
 
         Self {
             uuid,
             state: TaskState::Init,
             action,
             target_position,
-            time_left: 8.0,
+            elapsed: 0.0,
+            waited: 0.0,
+            preemptible,
         }
     }
     pub fn uuid(&self) -> Uuid {
@@ -53,7 +179,119 @@ impl Task {
     }
 
     pub fn time_left(&self) -> f64 {
-        self.time_left
+        (self.action.max_duration() - self.elapsed).max(0.0)
+    }
+
+    /// 0.0..=1.0. How close the primary need is to satisfied, or how much of max duration has passed.
+    pub fn progress(&self, needs: &PersonNeeds) -> f64 {
+        let time_progress = self.elapsed / self.action.max_duration();
+        let need_progress = match self.action.primary_need().and_then(|k| needs.get(k)) {
+            Some(value) => value / SATISFIED_NEED_THRESHOLD,
+            None => 0.0,
+        };
+        time_progress.max(need_progress).clamp(0.0, 1.0)
+    }
+
+    /// Primary need is satisfied or the action has gone on long enough.
+    pub fn is_finished(&self, needs: &PersonNeeds) -> bool {
+        if self.elapsed >= self.action.max_duration() {
+            return true;
+        }
+        self.action
+            .primary_need()
+            .and_then(|k| needs.get(k))
+            .is_some_and(|value| value >= SATISFIED_NEED_THRESHOLD)
+    }
+
+    /// Mark as done. False if it already was.
+    fn end(&mut self) -> bool {
+        let ended = self.state != TaskState::Done;
+        self.state = TaskState::Done;
+        ended
+    }
+
+    /// Spend `delta` more seconds in progress, doing to `needs` what the action does. Returns
+    /// whether the task is finished.
+    fn advance(&mut self, needs: &mut PersonNeeds, delta: f64) -> Result<bool, TaskError> {
+        self.elapsed += delta;
+
+        match self.action.key.as_str() {
+            "make_food" => {
+                needs.hunger += 0.2 * delta;
+            }
+            "toilet" => {
+                needs.bladder += 0.2 * delta;
+            }
+            "sit" => {
+                needs.comfort += 0.2 * delta;
+            }
+            "sleep" => {
+                needs.sleep += 0.2 * delta;
+                needs.comfort += 0.2 * delta;
+            }
+            "do_the_mario" => {
+                needs.fun += 0.2 * delta;
+                needs.social += 0.2 * delta;
+                needs.comfort += 0.2 * delta;
+                needs.hygiene -= 0.1 * delta;
+            }
+            "wash_hands" => {
+                needs.hygiene += 0.2 * delta;
+            }
+            "clean_puddle" => {
+                needs.hygiene -= 0.05 * delta;
+            }
+            "chat" => {
+                needs.social += 0.2 * delta;
+                needs.fun += 0.05 * delta;
+            }
+            "eat_together" => {
+                needs.hunger += 0.2 * delta;
+                needs.social += 0.1 * delta;
+            }
+            "group_chat" => {
+                needs.social += 0.2 * delta;
+                needs.fun += 0.05 * delta;
+            }
+            "joke" => {
+                needs.fun += 0.2 * delta;
+                needs.social += 0.1 * delta;
+            }
+            "hug" => {
+                needs.social += 0.3 * delta;
+                needs.comfort += 0.1 * delta;
+            }
+            "argue" => {
+                needs.social += 0.05 * delta;
+                needs.fun -= 0.05 * delta;
+            }
+            "pass_out" => {
+                needs.sleep += 0.1 * delta;
+            }
+            "light_on" | "light_off" | "accident" | "feel_gross" | "sulk" | "idle" => {}
+            key => return Err(TaskError::UnknownAction(key.into())),
+        }
+
+        Ok(self.is_finished(needs))
+    }
+
+    /// What the task leaves behind once it ran its course, see [Person::complete_task].
+    fn outcome(&self) -> TaskOutcome {
+        match self.action.key.as_str() {
+            "sleep" => TaskOutcome::WellRested,
+            "clean_puddle" => TaskOutcome::RemoveObject,
+            "light_on" => TaskOutcome::SwitchLamp { lit: true },
+            "light_off" => TaskOutcome::SwitchLamp { lit: false },
+            _ => TaskOutcome::Nothing,
+        }
+    }
+
+    /// How this task affects need decay in its current state.
+    pub fn need_decay(&self) -> NeedDecay {
+        match self.state {
+            TaskState::InProgress => self.action.need_decay(),
+            _ => NeedDecay::default(),
+        }
     }
 }
 
@@ -68,11 +306,21 @@ pub struct Person {
     possible_actions: Vec<String>,
     task: Task,
     action_queue: VecDeque<Action>,
+    buffs: Vec<Buff>,
+    /// None means the world default is used.
+    autonomy: Option<Autonomy>,
+    /// How this person feels about others.
+    relationships: HashMap<Uuid, Relationship>,
+    profile: PersonProfile,
 
     world: Gd<World>,
 
+    /// Position before the last tick, for smoothing out movement between ticks.
+    prev_position: Vector3,
+
     node_visuals: Gd<Node3D>,
     node_collider: Gd<EntityCollider>,
+    vfx_stink: Option<Gd<Node>>,
 
     base: Base<Node3D>,
 }
@@ -82,33 +330,97 @@ impl INode3D for Person {
     fn ready(&mut self) {
         self.setup_visuals();
         self.setup_collider();
+        self.prev_position = self.base().get_position();
     }
+}
+
+impl Person {
+    /// Advance the simulation by `delta` seconds. Driven by [World], not by the engine.
+    pub fn tick(&mut self, delta: f64) {
+        self.prev_position = self.base().get_position();
 
-    fn process(&mut self, delta: f64) {
-        self.needs.update(delta);
+        self.update_buffs(delta);
+        self.update_relationships(delta);
+        let decay = self.need_decay().combined(&self.weather_decay());
+        self.needs.update(delta, &decay);
+        self.update_environment(delta);
+        self.check_critical_needs();
+        self.check_urgent_needs();
+        self.update_stink();
+
+        if let Err(error) = self.process_task(delta) {
+            self.abandon_task(error);
+        }
+    }
+
+    /// Draw the model `alpha` of the way from the previous tick's position to the current one.
+    pub fn interpolate(&mut self, alpha: f32) {
+        let position = self.base().get_position();
+        let drawn = self.prev_position.lerp(position, alpha);
+        self.node_visuals.set_position(drawn - position);
+    }
+}
+
+#[godot_api]
+impl Person {
+    /// This person was selected.
+    #[signal]
+    fn sig_selected(person: Gd<Person>);
 
+    /// Current task ended, whether it was finished or interrupted.
+    #[signal]
+    fn sig_task_ended();
+
+    /// Removed from the world. Let go of everything and disappear.
+    /// Deferred by [World::remove_person], so the world can be looked up again.
+    #[func]
+    fn leave_world(&mut self) {
+        let notices = leaving_notices(&self.task, &self.action_queue);
+        self.action_queue.clear();
+        self.finish_task(false);
+
+        for (uuid, task_uuid) in notices {
+            let person = self.world.bind().get_person(&uuid).cloned();
+            if let Some(mut person) = person {
+                person.bind_mut().on_company_left(self.uuid, task_uuid);
+            }
+        }
+        self.base_mut().queue_free();
+    }
+
+    /// When [node_collider] is clicked.
+    #[func]
+    fn on_click(&mut self) {
+        let this = self.to_gd().to_variant();
+        self.base_mut().emit_signal("sig_selected", &[this]);
+    }
+}
+
+impl Person {
+    /// Advance the current task's state machine.
+    fn process_task(&mut self, delta: f64) -> Result<(), TaskError> {
         let mut position = self.base_mut().get_position();
         let mut this_gd = self.to_gd();
 
+        if self.task.state != TaskState::Done && !self.has_object(&self.task.action) {
+            return Err(TaskError::ObjectMissing);
+        }
+
         match self.task.state {
             TaskState::Init => {
                 self.task.state = TaskState::Moving;
             }
 
             TaskState::Moving => {
-                let Some(mut target_position) = self.task.target_position else {
+                let Some(target_position) = self.task_target_position(position) else {
                     self.task.state = TaskState::Waiting;
-                    return;
+                    return Ok(());
                 };
 
-                if !self.task.action.is_primary() {
-                    target_position += Vector3::RIGHT;
-                }
-
                 if position.distance_to(target_position) <= 0.5 {
                     this_gd.set_position(target_position);
                     self.task.state = TaskState::Waiting;
-                    return;
+                    return Ok(());
                 }
 
                 let dir = (target_position - position).normalized();
@@ -117,118 +429,121 @@ impl INode3D for Person {
                 self.base_mut().set_position(position);
             }
 
+            TaskState::Waiting if self.task.action.group.is_some() => {
+                self.wait_for_group(delta)?;
+            }
             TaskState::Waiting => match self.task.action.partner_uuid {
                 Some(partner_uuid) => {
-                    let world = self.world.bind();
-                    if let Some(partner) = world.get_person(&partner_uuid) {
-                        if partner.bind().task.action.master_uuid != Some(self.task.uuid) {
-                            return;
+                    let partner = self.world.bind().get_person(&partner_uuid).cloned();
+                    let partner = partner.ok_or(TaskError::PartnerMissing(partner_uuid))?;
+                    let (partner_task_uuid, partner_state) = {
+                        let partner = partner.bind();
+                        (partner.task.uuid, partner.task.state)
+                    };
+
+                    // Partner hasn't started our task yet
+                    if partner_task_uuid != self.task.uuid {
+                        self.task.waited += delta;
+                        if self.task.waited >= MAX_PARTNER_WAIT {
+                            return Err(TaskError::PartnerTimeout(partner_uuid));
                         }
-                        match partner.bind().task.state {
-                            TaskState::Init | TaskState::Moving => (),
-                            TaskState::Waiting | TaskState::InProgress => {
-                                self.task.state = TaskState::InProgress;
-
-                                if self.task.action.key.as_str() == "do_the_mario" {
-                                    let particles_packed: Gd<PackedScene> =
-                                        load("res://assets/prefabs/vfx_particle_hearts.tscn");
-                                    let particles = particles_packed.instantiate().unwrap();
-                                    this_gd.add_child(&particles);
-                                    this_gd.connect(
-                                        "sig_task_ended",
-                                        &particles.callable("queue_free"),
-                                    );
+                        return Ok(());
+                    }
+                    match partner_state {
+                        TaskState::Init | TaskState::Moving => (),
+                        TaskState::Waiting | TaskState::InProgress => {
+                            self.task.state = TaskState::InProgress;
+
+                            if self.task.action.is_social() {
+                                // Face to face
+                                let mut partner_position = partner.get_position();
+                                partner_position.y = position.y;
+                                if partner_position.distance_to(position) > 0.01 {
+                                    self.node_visuals.look_at(partner_position);
                                 }
                             }
-                            TaskState::Done => panic!("wtf, partner ended task before I began?"),
+
+                            if matches!(self.task.action.key.as_str(), "do_the_mario" | "hug") {
+                                let particles_packed: Gd<PackedScene> =
+                                    load("res://assets/prefabs/vfx_particle_hearts.tscn");
+                                let particles = particles_packed.instantiate().unwrap();
+                                this_gd.add_child(&particles);
+                                this_gd
+                                    .connect("sig_task_ended", &particles.callable("queue_free"));
+                            }
+                        }
+                        TaskState::Done => {
+                            return Err(TaskError::PartnerLeft(partner_uuid));
                         }
                     }
                 }
                 None => {
-                    if self.task.action.key.as_str() == "sleep" {
-                        let particles_packed: Gd<PackedScene> =
-                            load("res://assets/prefabs/vfx_particle_zzz.tscn");
-                        let particles = particles_packed.instantiate().unwrap();
-                        this_gd.add_child(&particles);
-                        this_gd.connect("sig_task_ended", &particles.callable("queue_free"));
+                    match self.task.action.key.as_str() {
+                        "sleep" | "pass_out" => {
+                            let particles_packed: Gd<PackedScene> =
+                                load("res://assets/prefabs/vfx_particle_zzz.tscn");
+                            let particles = particles_packed.instantiate().unwrap();
+                            this_gd.add_child(&particles);
+                            this_gd.connect("sig_task_ended", &particles.callable("queue_free"));
+
+                            if self.task.action.key.as_str() == "pass_out" {
+                                // Lie down on the floor
+                                let mut rotation = self.node_visuals.get_rotation_degrees();
+                                rotation.x = -90.0;
+                                self.node_visuals.set_rotation_degrees(rotation);
+                            }
+                        }
+                        "sulk" => {
+                            self.spawn_emote("Hmph!");
+                        }
+                        "accident" => {
+                            self.needs.set_bladder(1.0);
+                            self.world.bind_mut().spawn_puddle(position);
+                        }
+                        _ => (),
                     }
 
                     self.task.state = TaskState::InProgress;
                 }
             },
             TaskState::InProgress => {
-                self.task.time_left -= delta;
-
-                match self.task.action.key.as_str() {
-                    "make_food" => {
-                        self.needs.hunger += 0.2 * delta;
-                    }
-                    "toilet" => {
-                        self.needs.bladder += 0.2 * delta;
-                    }
-                    "sit" => {
-                        self.needs.comfort += 0.2 * delta;
-                    }
-                    "sleep" => {
-                        self.needs.sleep += 0.2 * delta;
-                        self.needs.comfort += 0.2 * delta;
-                    }
-                    "do_the_mario" => {
-                        self.needs.fun += 0.2 * delta;
-                        self.needs.social += 0.2 * delta;
-                        self.needs.comfort += 0.2 * delta;
-                        self.needs.hygiene -= 0.1 * delta;
-                    }
-                    "wash_hands" => {
-                        self.needs.hygiene += 0.2 * delta;
-                    }
-                    "idle" => {}
-                    _ => panic!("unpossible!"),
+                for uuid in self.task.action.company() {
+                    self.relationships
+                        .entry(uuid)
+                        .or_default()
+                        .apply_action(&self.task.action.key, delta);
                 }
 
-                if self.task.time_left <= 0.0 {
-                    self.end_task();
+                if self.task.advance(&mut self.needs, delta)? {
+                    self.complete_task();
                 }
             }
             TaskState::Done => {
-                // Clean up old task
-                let old_action = &mut self.task.action;
-                if let Some(old_target) = &mut old_action.object {
-                    old_target.bind_mut().unreserve();
-                }
-                if let Some(partner_uuid) = old_action.partner_uuid {
-                    let mut partner = self.world.bind().get_person(&partner_uuid).unwrap().clone();
-                    partner.bind_mut().end_task_uuid(self.task.uuid);
-                }
-
                 self.start_new_task();
             }
         }
+        Ok(())
     }
-}
 
-#[godot_api]
-impl Person {
-    /// This person was selected.
-    #[signal]
-    fn sig_selected(person: Gd<Person>);
-
-    #[signal]
-    fn sig_task_ended();
+    /// Something went wrong with the current task. Drop it and carry on with life.
+    fn abandon_task(&mut self, error: TaskError) {
+        godot_warn!(
+            "{} abandoned `{}`: {error}",
+            self.base().get_name(),
+            self.task.action.key
+        );
+        self.end_task();
+    }
 
-    /// When [node_collider] is clicked.
-    #[func]
-    fn on_click(&mut self) {
-        let this = self.to_gd().to_variant();
-        self.base_mut().emit_signal("sig_selected", &[this]);
+    pub fn new(world: Gd<World>, profile: PersonProfile) -> Gd<Self> {
+        Self::with_uuid(world, Uuid::new_v4(), profile)
     }
-}
 
-impl Person {
-    pub fn new(world: Gd<World>) -> Gd<Self> {
-        let uuid = Uuid::new_v4();
+    /// Someone who already existed, like a person from a save.
+    pub fn with_uuid(world: Gd<World>, uuid: Uuid, profile: PersonProfile) -> Gd<Self> {
+        let name = profile.full_name();
 
-        Gd::from_init_fn(|base| Self {
+        let mut person = Gd::from_init_fn(|base| Self {
             uuid,
 
             needs: PersonNeeds::default(),
@@ -236,22 +551,39 @@ impl Person {
             possible_actions: vec![
                 "make_food".into(),
                 "toilet".into(),
+                "clean_puddle".into(),
+                "light_on".into(),
+                "light_off".into(),
                 "sit".into(),
                 "sleep".into(),
                 //"do_the_mario".into(),
                 "wash_hands".into(),
+                "chat".into(),
+                "group_chat".into(),
+                "eat_together".into(),
+                "joke".into(),
+                "hug".into(),
+                "argue".into(),
                 "idle".into(),
             ],
             task: Task::new(Action::idle()),
             action_queue: VecDeque::new(),
+            buffs: vec![],
+            autonomy: None,
+            relationships: HashMap::new(),
+            profile,
 
             world,
 
+            prev_position: Vector3::ZERO,
             node_visuals: Node3D::new_alloc(),
             node_collider: EntityCollider::new_alloc(),
+            vfx_stink: None,
 
             base,
-        })
+        });
+        person.set_name(&name);
+        person
     }
 
     pub fn uuid(&self) -> Uuid {
@@ -270,8 +602,169 @@ impl Person {
         &self.task
     }
 
+    /// 0.0..=1.0. Needs plus moodlets.
+    pub fn mood(&self) -> f64 {
+        let moodlets: f64 = self.buffs.iter().map(|b| b.mood).sum();
+        (self.needs.mood() + moodlets).clamp(0.0, 1.0)
+    }
+
+    /// Social actions others can do with this person.
+    pub fn social_advertisements(uuid: Uuid) -> Vec<ActionAdvertisement> {
+        let advert = |key: &str, stats: &[(&str, isize)]| ActionAdvertisement {
+            source: ActionAdvertisementSource::Person(uuid),
+            action_key: key.into(),
+            stats: stats
+                .iter()
+                .map(|(key, value)| ActionAdvertisementStat {
+                    key: (*key).into(),
+                    value: *value,
+                })
+                .collect(),
+            required_people: 2,
+        };
+
+        vec![
+            advert("chat", &[("social", 6), ("fun", 2)]),
+            advert("joke", &[("fun", 6), ("social", 3)]),
+            advert("hug", &[("social", 8), ("comfort", 2)]),
+            advert("argue", &[("social", 2), ("fun", -2)]),
+            ActionAdvertisement {
+                required_people: 4,
+                ..advert("group_chat", &[("social", 7), ("fun", 3)])
+            },
+        ]
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        matches!(self.task.action.key.as_str(), "sleep" | "pass_out")
+            && self.task.state != TaskState::Done
+    }
+
+    /// Not busy with anything that shouldn't be interrupted by a social invite. Long tasks count
+    /// as busy too, the one asking would give up before they're over.
+    pub fn is_available(&self) -> bool {
+        self.task.action.priority == ActionPriority::Autonomous
+            && self.task.action.can_be_cancelled()
+            && self.task.time_left() <= MAX_PARTNER_WAIT
+            && self.task.action.partner_uuid.is_none()
+            && self.task.action.group.is_none()
+            && self.action_queue.is_empty()
+    }
+
+    /// Seed the AI's random numbers. Done by [World::add_person].
+    pub fn seed_rng(&mut self, seed: u64) {
+        self.brain.seed_rng(seed);
+    }
+
+    pub fn profile(&self) -> &PersonProfile {
+        &self.profile
+    }
+
+    pub fn world(&self) -> &Gd<World> {
+        &self.world
+    }
+
+    pub fn relationships(&self) -> &HashMap<Uuid, Relationship> {
+        &self.relationships
+    }
+
+    fn update_relationships(&mut self, delta: f64) {
+        let company = match self.task.state {
+            TaskState::InProgress => self.task.action.company(),
+            _ => vec![],
+        };
+        for (uuid, relationship) in &mut self.relationships {
+            if !company.contains(uuid) {
+                relationship.decay(delta);
+            }
+        }
+    }
+
+    pub fn autonomy(&self) -> Option<Autonomy> {
+        self.autonomy
+    }
+
+    pub fn set_autonomy(&mut self, autonomy: Option<Autonomy>) {
+        self.autonomy = autonomy;
+    }
+
+    /// Own autonomy setting, or the world default. People outside the active household are
+    /// always on their own.
+    pub fn effective_autonomy(&self) -> Autonomy {
+        let world = self.world.bind();
+        if !world.is_playable(&self.uuid) {
+            return Autonomy::Full;
+        }
+        self.autonomy.unwrap_or_else(|| world.autonomy())
+    }
+
+    pub fn to_save(&self) -> PersonSave {
+        let position = self.base().get_position();
+        PersonSave {
+            uuid: self.uuid,
+            name: self.profile.full_name(),
+            position: [position.x, position.y, position.z],
+            needs: self.needs.clone(),
+            autonomy: self.autonomy,
+            profile: self.profile.clone(),
+            buffs: self.buffs.clone(),
+            relationships: self.relationships.clone(),
+        }
+    }
+
+    /// Tasks aren't saved, so whatever this person was doing is dropped. Everyone else in the
+    /// save drops theirs too, so partners aren't told.
+    pub fn apply_save(&mut self, save: &PersonSave) {
+        self.action_queue.clear();
+        self.finish_task(false);
+
+        let [x, y, z] = save.position;
+        self.base_mut().set_position(Vector3::new(x, y, z));
+        self.prev_position = Vector3::new(x, y, z);
+        self.needs = save.needs.clone();
+        self.autonomy = save.autonomy;
+        self.buffs = save.buffs.clone();
+        self.relationships = save.relationships.clone();
+        self.profile = save.profile.clone();
+        self.apply_appearance();
+    }
+
+    pub fn buffs(&self) -> &Vec<Buff> {
+        &self.buffs
+    }
+
+    pub fn add_buff(&mut self, buff: Buff) {
+        self.buffs.retain(|b| b.key != buff.key);
+        self.buffs.push(buff);
+    }
+
+    /// Combined need decay multipliers from current task, traits and buffs.
+    pub fn need_decay(&self) -> NeedDecay {
+        let base = self.task.need_decay().combined(&self.profile.need_decay());
+        self.buffs
+            .iter()
+            .fold(base, |decay, buff| decay.combined(&buff.decay))
+    }
+
+    /// Bad weather wears on people who are out in it.
+    fn weather_decay(&self) -> NeedDecay {
+        let position = self.base().get_global_position();
+        let world = self.world.bind();
+        match world.room_at(position) {
+            Some(_) => NeedDecay::default(),
+            None => world.weather().need_decay(),
+        }
+    }
+
+    fn update_buffs(&mut self, delta: f64) {
+        for buff in &mut self.buffs {
+            buff.time_left -= delta;
+        }
+        self.buffs.retain(|b| !b.is_expired());
+    }
+
     fn setup_visuals(&mut self) {
-        let person_packed: Gd<PackedScene> = load("res://assets/characters/mdl_person_base.blend");
+        let person_packed: Gd<PackedScene> = load("res://assets/prefabs/mdl_person.tscn");
         let mut person_model = person_packed.instantiate().unwrap();
         person_model.set_name("person_model");
 
@@ -280,6 +773,53 @@ impl Person {
         node_visuals.set_name("visuals");
 
         self.base_mut().add_child(&node_visuals);
+        self.apply_appearance();
+    }
+
+    /// Tint and scale the model to match the profile. Hair goes on the model's hair socket.
+    fn apply_appearance(&mut self) {
+        const HAIR_RADIUS: f32 = 0.13;
+
+        self.node_visuals.set_scale(self.profile.body_scale());
+
+        let base_mat: Gd<StandardMaterial3D> = load("res://assets/characters/mat_person_base.tres");
+        let Some(mat) = base_mat.duplicate() else {
+            return;
+        };
+        let mut mat = mat.cast::<StandardMaterial3D>();
+        mat.set_albedo(self.profile.skin_color());
+
+        let meshes = self
+            .node_visuals
+            .find_children_ex("*")
+            .type_("MeshInstance3D")
+            .owned(false)
+            .done();
+        for mesh in meshes.iter_shared() {
+            mesh.cast::<MeshInstance3D>().set_material_override(&mat);
+        }
+
+        let mut hair_mat = StandardMaterial3D::new_gd();
+        hair_mat.set_albedo(self.profile.hair_color());
+
+        // Not there until the model is set up
+        let Some(mut socket) = self
+            .node_visuals
+            .get_node_or_null("person_model/HairSocket")
+        else {
+            return;
+        };
+        let mut hair = match socket.get_node_or_null("hair") {
+            Some(hair) => hair.cast::<CsgSphere3D>(),
+            None => {
+                let mut hair = CsgSphere3D::new_alloc();
+                hair.set_name("hair");
+                socket.add_child(&hair);
+                hair
+            }
+        };
+        hair.set_radius(HAIR_RADIUS);
+        hair.set_material_override(&hair_mat);
     }
 
     fn setup_collider(&mut self) {
@@ -294,54 +834,798 @@ impl Person {
         self.action_queue.push_back(action);
     }
 
+    /// Someone asks this person to join `action`. Accepted actions start right away, deferred ones
+    /// are queued for later.
+    pub fn consider_proposal(&mut self, from: Uuid, action: Action) -> ProposalResponse {
+        let relationship = self.relationships.get(&from).copied().unwrap_or_default();
+        let has_other_urgent_need = self
+            .needs
+            .most_urgent(URGENT_NEED_THRESHOLD)
+            .is_some_and(|need| action.primary_need() != Some(need));
+        let busy = !self.is_available() || has_other_urgent_need;
+
+        let response =
+            self.brain
+                .evaluate_proposal(&action, &self.needs, self.mood(), &relationship, busy);
+        match response {
+            ProposalResponse::Accept => self.interrupt_task(action),
+            ProposalResponse::Defer => self.queue_action(action),
+            ProposalResponse::Reject => (),
+        }
+        response
+    }
+
+    /// Turned down by `partner_uuid`. Stings a bit.
+    fn on_rejected(&mut self, partner_uuid: Uuid) {
+        const FRIENDSHIP_HIT: f64 = 0.1;
+
+        let relationship = self.relationships.entry(partner_uuid).or_default();
+        relationship.friendship = (relationship.friendship - FRIENDSHIP_HIT).clamp(-1.0, 1.0);
+        self.add_buff(Buff::embarrassed());
+    }
+
+    /// Floating text above the head until the current task ends.
+    fn spawn_emote(&mut self, text: &str) {
+        let mut emote = Label3D::new_alloc();
+        emote.set_text(text);
+        emote.set_billboard_mode(BillboardMode::ENABLED);
+        emote.set_position(Vector3::UP * 2.0);
+        emote.set_name("emote");
+
+        let mut this_gd = self.to_gd();
+        this_gd.add_child(&emote);
+        this_gd.connect("sig_task_ended", &emote.callable("queue_free"));
+    }
+
+    /// Where to walk for the current task, if anywhere.
+    fn task_target_position(&self, position: Vector3) -> Option<Vector3> {
+        if let Some(group) = &self.task.action.group {
+            return self.group_slot_position(group);
+        }
+
+        if let Some(target_position) = self.task.target_position {
+            if !self.task.action.is_primary() {
+                return Some(target_position + Vector3::RIGHT);
+            }
+            return Some(target_position);
+        }
+
+        // Initiator of a social action walks up to the partner, who stays put.
+        if self.task.action.is_social() && self.task.action.is_primary() {
+            let partner_uuid = self.task.action.partner_uuid?;
+            let partner_position = self.world.bind().get_person(&partner_uuid)?.get_position();
+            let away = (position - partner_position)
+                .try_normalized()
+                .unwrap_or(Vector3::RIGHT);
+            return Some(partner_position + away * SOCIAL_DISTANCE);
+        }
+
+        None
+    }
+
+    /// Where this person stands in a group, evenly spaced around the leader's target position.
+    fn group_slot_position(&self, group: &GroupRole) -> Option<Vector3> {
+        let (center, slot, slots) = match group {
+            GroupRole::Leader { slots, .. } => (self.task.target_position?, 0, *slots),
+            GroupRole::Member { leader_uuid, slot } => {
+                let leader = self.world.bind().get_person(leader_uuid)?.clone();
+                let leader = leader.bind();
+                let Some(GroupRole::Leader { slots, .. }) = &leader.task.action.group else {
+                    return None;
+                };
+                (leader.task.target_position?, *slot, *slots)
+            }
+        };
+        let angle = std::f32::consts::TAU * slot as f32 / slots.max(1) as f32;
+        Some(center + Vector3::new(angle.cos(), 0.0, angle.sin()) * GROUP_RADIUS)
+    }
+
+    /// Gathering phase of a group activity. The leader starts once every member has arrived,
+    /// or gives up on stragglers after a while. Members start when the leader does.
+    fn wait_for_group(&mut self, delta: f64) -> Result<(), TaskError> {
+        let task_uuid = self.task.uuid;
+        let world = self.world.clone();
+
+        let ready = match &self.task.action.group {
+            Some(GroupRole::Leader { members, .. }) => {
+                let world = world.bind();
+                let stragglers: Vec<Uuid> = members
+                    .iter()
+                    .filter(|uuid| {
+                        !world.get_person(uuid).is_some_and(|person| {
+                            let person = person.bind();
+                            person.task.uuid == task_uuid
+                                && matches!(
+                                    person.task.state,
+                                    TaskState::Waiting | TaskState::InProgress
+                                )
+                        })
+                    })
+                    .copied()
+                    .collect();
+                drop(world);
+
+                if stragglers.is_empty() {
+                    true
+                } else {
+                    self.task.waited += delta;
+                    if self.task.waited < MAX_PARTNER_WAIT {
+                        return Ok(());
+                    }
+                    for uuid in stragglers {
+                        self.drop_group_member(uuid);
+                    }
+                    if !self.has_group_quorum() {
+                        self.end_task();
+                        return Ok(());
+                    }
+                    true
+                }
+            }
+            Some(GroupRole::Member { leader_uuid, .. }) => {
+                let leader = world.bind().get_person(leader_uuid).cloned();
+                let leader = leader.ok_or(TaskError::PartnerMissing(*leader_uuid))?;
+                let leader = leader.bind();
+                if leader.task.uuid != task_uuid {
+                    return Err(TaskError::PartnerLeft(*leader_uuid));
+                }
+                let started = leader.task.state == TaskState::InProgress;
+                drop(leader);
+                if !started {
+                    self.task.waited += delta;
+                    if self.task.waited >= MAX_PARTNER_WAIT * 2.0 {
+                        return Err(TaskError::PartnerTimeout(*leader_uuid));
+                    }
+                    return Ok(());
+                }
+                true
+            }
+            None => false,
+        };
+
+        if ready {
+            self.task.state = TaskState::InProgress;
+
+            // Face the middle of the group
+            let center = match &self.task.action.group {
+                Some(GroupRole::Leader { .. }) => self.task.target_position,
+                _ => self.group_center(),
+            };
+            if let Some(mut center) = center {
+                let position = self.base().get_position();
+                center.y = position.y;
+                if center.distance_to(position) > 0.01 {
+                    self.node_visuals.look_at(center);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Center of the group this person is a member of.
+    fn group_center(&self) -> Option<Vector3> {
+        let Some(GroupRole::Member { leader_uuid, .. }) = &self.task.action.group else {
+            return None;
+        };
+        let leader = self.world.bind().get_person(leader_uuid)?.clone();
+        let center = leader.bind().task.target_position;
+        center
+    }
+
+    fn has_group_quorum(&self) -> bool {
+        self.task
+            .action
+            .group
+            .as_ref()
+            .is_none_or(|group| group.has_quorum())
+    }
+
+    /// Leader kicks a member out of the group, ending their copy of the task.
+    fn drop_group_member(&mut self, uuid: Uuid) {
+        if let Some(GroupRole::Leader { members, .. }) = &mut self.task.action.group {
+            members.retain(|m| *m != uuid);
+        }
+        let member = self.world.bind().get_person(&uuid).cloned();
+        if let Some(mut member) = member {
+            member.bind_mut().on_company_left(self.uuid, self.task.uuid);
+        }
+    }
+
+    /// Player wants this done. Autonomous busywork gets dropped for it, if it can be cancelled.
+    /// Otherwise it waits its turn in the queue.
+    pub fn queue_player_action(&mut self, mut action: Action) {
+        action.priority = ActionPriority::Player;
+        self.action_queue.push_back(action);
+
+        if self.task.action.priority == ActionPriority::Autonomous {
+            self.cancel_task();
+        }
+    }
+
+    /// Drop the current task and do `action` next.
+    fn interrupt_task(&mut self, action: Action) {
+        self.action_queue.push_front(action);
+        self.end_task();
+    }
+
+    /// An urgent need interrupts whatever unimportant thing the person was doing. What counts as
+    /// unimportant follows the current autonomy, changing it applies to the task at hand too.
+    fn check_urgent_needs(&mut self) {
+        if !self.task.preemptible || self.task.state == TaskState::Done {
+            return;
+        }
+        let Some(need) = self.needs.most_urgent(URGENT_NEED_THRESHOLD) else {
+            return;
+        };
+        if self.task.action.primary_need() == Some(need) {
+            return;
+        }
+        if !self
+            .effective_autonomy()
+            .can_preempt(self.task.action.priority)
+        {
+            return;
+        }
+
+        self.end_task();
+    }
+
+    /// A need that hits rock bottom forces the person to drop what they're doing.
+    fn check_critical_needs(&mut self) {
+        let forced_key = if self.needs.sleep() <= 0.0 {
+            "pass_out"
+        } else if self.needs.bladder() <= 0.0 {
+            "accident"
+        } else if self.needs.hygiene() <= 0.0 && self.vfx_stink.is_none() {
+            "feel_gross"
+        } else {
+            return;
+        };
+
+        let already_forced = self.task.action.key == forced_key
+            || self.action_queue.iter().any(|a| a.key == forced_key);
+        if already_forced {
+            return;
+        }
+
+        let mut action = Action::in_place(forced_key);
+        action.priority = ActionPriority::Forced;
+        self.interrupt_task(action);
+    }
+
+    /// Stinky people emit stink.
+    fn update_stink(&mut self) {
+        const CLEAN_THRESHOLD: f64 = 0.3;
+
+        let hygiene = self.needs.hygiene();
+        match &mut self.vfx_stink {
+            Some(vfx) if hygiene >= CLEAN_THRESHOLD => {
+                vfx.queue_free();
+                self.vfx_stink = None;
+            }
+            None if hygiene <= 0.0 => {
+                let particles_packed: Gd<PackedScene> =
+                    load("res://assets/prefabs/vfx_particle_stink.tscn");
+                let mut particles = particles_packed.instantiate().unwrap();
+                particles.set_name("vfx_stink");
+                self.base_mut().add_child(&particles);
+                self.vfx_stink = Some(particles);
+            }
+            _ => (),
+        }
+    }
+
+    /// Environment slowly follows the surroundings.
+    fn update_environment(&mut self, delta: f64) {
+        const RATE: f64 = 0.2;
+
+        let position = self.base().get_global_position();
+        let target = self.world.bind().environment_at(position);
+        let environment = self.needs.environment();
+        self.needs
+            .set_environment(environment + (target - environment) * RATE * delta);
+    }
+
+    pub fn action_queue(&self) -> &VecDeque<Action> {
+        &self.action_queue
+    }
+
+    /// Player cancels the current task. Returns false if it can't be cancelled.
+    pub fn cancel_task(&mut self) -> bool {
+        if !self.task.action.can_be_cancelled() {
+            return false;
+        }
+        self.end_task();
+        true
+    }
+
+    /// Player cancels a queued action. Returns false if it can't be cancelled.
+    pub fn cancel_queued(&mut self, index: usize) -> bool {
+        if !self
+            .action_queue
+            .get(index)
+            .is_some_and(|a| a.can_be_cancelled())
+        {
+            return false;
+        }
+        let Some(action) = self.action_queue.remove(index) else {
+            return false;
+        };
+
+        // Someone is waiting for us to join their task.
+        if let Some(master_uuid) = action.master_uuid {
+            self.notify_company(&action, master_uuid);
+        }
+        true
+    }
+
+    /// Let everyone else in `action` know this person is no longer part of task `task_uuid`.
+    fn notify_company(&self, action: &Action, task_uuid: Uuid) {
+        let company: Vec<Gd<Person>> = {
+            let world = self.world.bind();
+            action
+                .company()
+                .iter()
+                .filter_map(|uuid| world.get_person(uuid).cloned())
+                .collect()
+        };
+        for mut person in company {
+            person.bind_mut().on_company_left(self.uuid, task_uuid);
+        }
+    }
+
+    /// The current task ran its course. Apply its lasting effects, then end it.
+    fn complete_task(&mut self) {
+        match self.task.outcome() {
+            TaskOutcome::Nothing => (),
+            TaskOutcome::WellRested => self.add_buff(Buff::well_rested()),
+            TaskOutcome::RemoveObject => {
+                if let Some(object) = &self.task.action.object {
+                    self.world.bind_mut().remove_furniture(object);
+                }
+            }
+            TaskOutcome::SwitchLamp { lit } => {
+                if let Some(mut lamp) = self.task.action.object.clone() {
+                    self.world.bind_mut().set_lamp_lit(&mut lamp, lit);
+                }
+            }
+        }
+        self.end_task();
+    }
+
+    /// Is the furniture `action` is about still in the world, if it's about any.
+    fn has_object(&self, action: &Action) -> bool {
+        action
+            .object
+            .as_ref()
+            .is_none_or(|object| self.world.bind().has_furniture(object))
+    }
+
+    /// Forget queued actions on furniture that's gone, like a puddle someone else cleaned up.
+    fn drop_stale_actions(&mut self) {
+        let (queue, stale): (Vec<Action>, Vec<Action>) = std::mem::take(&mut self.action_queue)
+            .into_iter()
+            .partition(|action| self.has_object(action));
+        self.action_queue = queue.into();
+        for action in stale {
+            if let Some(master_uuid) = action.master_uuid {
+                self.notify_company(&action, master_uuid);
+            }
+        }
+    }
+
+    /// End the current task, and the partner's copy of it.
     fn end_task(&mut self) {
-        self.task.state = TaskState::Done;
+        self.finish_task(true);
     }
 
-    pub fn end_task_uuid(&mut self, uuid: Uuid) {
-        if self.task.uuid == uuid {
-            self.task.state = TaskState::Done;
+    /// `leaver` is no longer part of task `task_uuid`. End our copy of it, or forget it if we
+    /// hadn't started yet. Groups carry on while they have quorum.
+    pub fn on_company_left(&mut self, leaver: Uuid, task_uuid: Uuid) {
+        match company_left(&mut self.task, &mut self.action_queue, leaver, task_uuid) {
+            CompanyLeft::CarryOn => (),
+            CompanyLeft::EndTask => self.clean_up_task(false),
+            CompanyLeft::BreakUp => self.clean_up_task(true),
         }
     }
 
+    /// Clean up the current task. A new one will be started on next process.
+    fn finish_task(&mut self, notify_partner: bool) {
+        if self.task.end() {
+            self.clean_up_task(notify_partner);
+        }
+    }
+
+    /// Get up, let go of the object and let others know the task that just ended is over.
+    fn clean_up_task(&mut self, notify_partner: bool) {
+        if self.task.action.key == "pass_out" {
+            // Get up
+            let mut rotation = self.node_visuals.get_rotation_degrees();
+            rotation.x = 0.0;
+            self.node_visuals.set_rotation_degrees(rotation);
+        }
+
+        if self.task.action.is_primary() {
+            if let Some(target) = &mut self.task.action.object {
+                // Might've been freed along with the rest of a removed object
+                if target.is_instance_valid() {
+                    target.bind_mut().unreserve();
+                }
+            }
+        }
+
+        if notify_partner {
+            self.notify_company(&self.task.action, self.task.uuid);
+        }
+
+        self.base_mut().emit_signal("sig_task_ended", &[]);
+    }
+
     fn start_new_task(&mut self) {
         let this_gd = self.to_gd();
 
-        self.base_mut().emit_signal("sig_task_ended", &[]);
-        self.task = self.find_new_task();
+        self.task = match self.find_new_task() {
+            Ok(task) => task,
+            Err(error) => {
+                godot_warn!("{} couldn't start a task: {error}", self.base().get_name());
+                Task::new(Action::idle())
+            }
+        };
 
         self.brain.last_action = self.task.action.key.clone();
-        if let Some(target) = &mut self.task.action.object {
-            target.bind_mut().reserve(this_gd.clone());
+        if self.task.action.is_primary() {
+            if let Some(target) = &mut self.task.action.object {
+                target.bind_mut().reserve(this_gd.clone());
+            }
         }
     }
 
-    fn find_new_task(&mut self) -> Task {
+    fn find_new_task(&mut self) -> Result<Task, TaskError> {
+        let autonomy = self.effective_autonomy();
+        let urgent_need = self.needs.most_urgent(URGENT_NEED_THRESHOLD);
+
+        self.drop_stale_actions();
         let action = match self.action_queue.pop_front() {
             Some(action) => action,
-            None => self.brain.decide_action(
-                &self.needs,
-                &self.world.bind().advertisements(),
-                self.world.bind().people(),
-                &self.possible_actions,
-            ),
+            None => match autonomy {
+                Autonomy::Off => Action::idle(),
+                Autonomy::Low if urgent_need.is_none() => Action::idle(),
+                Autonomy::Low | Autonomy::Full => {
+                    let (advertisements, people) = {
+                        let world = self.world.bind();
+                        (world.advertisements(), world.people_ordered())
+                    };
+                    // Low autonomy only sees to the urgent need
+                    let need = match autonomy {
+                        Autonomy::Low => urgent_need,
+                        _ => None,
+                    };
+                    self.brain.decide_action(
+                        &self.needs,
+                        &advertisements,
+                        &people,
+                        &self.relationships,
+                        &self.possible_actions,
+                        need,
+                    )
+                }
+            },
         };
 
-        let task = Task::new(action);
+        let mut task = Task::new(action);
 
-        // Send a secondary copy to partner of group activity.
+        // This was already chosen with the urgent need in mind, don't thrash.
+        if urgent_need.is_some() {
+            task.preemptible = false;
+        }
+
+        // Ask partner of group activity to join with a secondary copy.
         if task.action.is_primary() {
             if let Some(partner_uuid) = task.action.partner_uuid {
                 let mut secondary_action = task.action.clone();
 
-                let mut partner = self.world.bind().get_person(&partner_uuid).unwrap().clone();
+                let partner = self.world.bind().get_person(&partner_uuid).cloned();
+                let mut partner = partner.ok_or(TaskError::PartnerMissing(partner_uuid))?;
                 secondary_action.partner_uuid = Some(self.uuid);
                 secondary_action.master_uuid = Some(task.uuid());
 
-                partner.bind_mut().queue_action(secondary_action);
+                let response = partner
+                    .bind_mut()
+                    .consider_proposal(self.uuid, secondary_action);
+                if response == ProposalResponse::Reject {
+                    self.on_rejected(partner_uuid);
+                    return Ok(Task::new(Action::in_place("sulk")));
+                }
             }
         }
 
-        task
+        // Invite every member of a group activity to their slot.
+        if let Some(GroupRole::Leader { members, .. }) = task.action.group.clone() {
+            if task.target_position.is_none() {
+                task.target_position = Some(self.base().get_position());
+            }
+
+            for (i, uuid) in members.into_iter().enumerate() {
+                let member = self.world.bind().get_person(&uuid).cloned();
+                let response = match member.clone() {
+                    Some(mut member) => {
+                        let mut member_action = task.action.clone();
+                        member_action.master_uuid = Some(task.uuid());
+                        member_action.group = Some(GroupRole::Member {
+                            leader_uuid: self.uuid,
+                            slot: i + 1,
+                        });
+                        member
+                            .bind_mut()
+                            .consider_proposal(self.uuid, member_action)
+                    }
+                    None => ProposalResponse::Reject,
+                };
+
+                if response == ProposalResponse::Reject {
+                    if let Some(GroupRole::Leader { members, .. }) = &mut task.action.group {
+                        members.retain(|m| *m != uuid);
+                    }
+                    if member.is_some() {
+                        self.on_rejected(uuid);
+                    }
+                }
+            }
+
+            // The quorum is counted from those who said yes
+            if let Some(GroupRole::Leader {
+                members, joined, ..
+            }) = &mut task.action.group
+            {
+                *joined = members.len();
+            }
+            let nobody_came = task.action.group.as_ref().is_some_and(|g| !g.has_quorum());
+            if nobody_came {
+                return Ok(Task::new(Action::in_place("sulk")));
+            }
+        }
+
+        Ok(task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Our copy of a pair action that `partner` started as task `task_uuid`.
+    fn partner_action(partner: Uuid, task_uuid: Uuid) -> Action {
+        Action {
+            partner_uuid: Some(partner),
+            master_uuid: Some(task_uuid),
+            ..Action::in_place("chat")
+        }
+    }
+
+    #[test]
+    fn partner_removed_while_waiting() {
+        let (partner, task_uuid) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut task = Task::new(partner_action(partner, task_uuid));
+        task.state = TaskState::Waiting;
+        let mut queue = VecDeque::new();
+
+        let result = company_left(&mut task, &mut queue, partner, task_uuid);
+        assert_eq!(result, CompanyLeft::EndTask);
+        assert_eq!(task.state(), TaskState::Done);
+    }
+
+    #[test]
+    fn partner_removed_in_progress() {
+        let (partner, task_uuid) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut task = Task::new(partner_action(partner, task_uuid));
+        task.state = TaskState::InProgress;
+        let mut queue = VecDeque::from([Action::in_place("sit")]);
+
+        let result = company_left(&mut task, &mut queue, partner, task_uuid);
+        assert_eq!(result, CompanyLeft::EndTask);
+        assert_eq!(task.state(), TaskState::Done);
+        assert_eq!(queue.len(), 1, "unrelated actions stay queued");
+    }
+
+    #[test]
+    fn partner_removed_while_queued() {
+        let (partner, task_uuid) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut task = Task::new(Action::in_place("sit"));
+        task.state = TaskState::InProgress;
+        let mut queue = VecDeque::from([
+            Action::in_place("toilet"),
+            partner_action(partner, task_uuid),
+        ]);
+
+        let result = company_left(&mut task, &mut queue, partner, task_uuid);
+        assert_eq!(result, CompanyLeft::CarryOn);
+        assert_eq!(task.state(), TaskState::InProgress);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].key, "toilet");
+    }
+
+    #[test]
+    fn finished_task_is_left_alone() {
+        let (partner, task_uuid) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut task = Task::new(partner_action(partner, task_uuid));
+        task.state = TaskState::Done;
+
+        let result = company_left(&mut task, &mut VecDeque::new(), partner, task_uuid);
+        assert_eq!(result, CompanyLeft::CarryOn);
+    }
+
+    #[test]
+    fn group_breaks_up_without_quorum() {
+        let members: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let mut task = Task::new(Action {
+            group: Some(GroupRole::Leader {
+                slots: 5,
+                members: members.clone(),
+                joined: members.len(),
+            }),
+            ..Action::in_place("group_chat")
+        });
+        task.state = TaskState::InProgress;
+        let task_uuid = task.uuid();
+        let mut queue = VecDeque::new();
+
+        for leaver in &members[..2] {
+            let result = company_left(&mut task, &mut queue, *leaver, task_uuid);
+            assert_eq!(result, CompanyLeft::CarryOn);
+        }
+        let result = company_left(&mut task, &mut queue, members[2], task_uuid);
+        assert_eq!(result, CompanyLeft::BreakUp);
+        assert_eq!(task.state(), TaskState::Done);
+    }
+
+    /// Run `task` in progress until it's finished, as [Person::process_task] does.
+    fn run_to_completion(task: &mut Task, needs: &mut PersonNeeds) -> Result<(), TaskError> {
+        task.state = TaskState::InProgress;
+        for _ in 0..100_000 {
+            if task.advance(needs, 0.1)? {
+                return Ok(());
+            }
+        }
+        panic!("`{}` never finished", task.action.key);
+    }
+
+    #[test]
+    fn switching_a_lamp_runs_its_course() {
+        for (key, lit) in [("light_on", true), ("light_off", false)] {
+            let mut task = Task::new(Action::in_place(key));
+            run_to_completion(&mut task, &mut PersonNeeds::default()).unwrap();
+            assert_eq!(task.outcome(), TaskOutcome::SwitchLamp { lit });
+        }
+    }
+
+    #[test]
+    fn every_possible_action_runs_its_course() {
+        let keys = [
+            "make_food",
+            "toilet",
+            "clean_puddle",
+            "light_on",
+            "light_off",
+            "sit",
+            "sleep",
+            "do_the_mario",
+            "wash_hands",
+            "chat",
+            "group_chat",
+            "eat_together",
+            "joke",
+            "hug",
+            "argue",
+            "pass_out",
+            "accident",
+            "feel_gross",
+            "sulk",
+            "idle",
+        ];
+        for key in keys {
+            let mut task = Task::new(Action::in_place(key));
+            let mut needs = PersonNeeds::default();
+            assert_eq!(run_to_completion(&mut task, &mut needs), Ok(()), "`{key}`");
+        }
+    }
+
+    #[test]
+    fn unknown_action_fails() {
+        let mut task = Task::new(Action::in_place("juggle"));
+        let result = run_to_completion(&mut task, &mut PersonNeeds::default());
+        assert_eq!(result, Err(TaskError::UnknownAction("juggle".into())));
+    }
+
+    /// Deliver what `leaver` tells others on leaving the world to `person`, as
+    /// [Person::leave_world] and [Person::on_company_left] do.
+    fn leave(
+        leaver: Uuid,
+        leaver_task: &Task,
+        leaver_queue: &VecDeque<Action>,
+        person: Uuid,
+        task: &mut Task,
+        queue: &mut VecDeque<Action>,
+    ) {
+        for (uuid, task_uuid) in leaving_notices(leaver_task, leaver_queue) {
+            if uuid == person {
+                company_left(task, queue, leaver, task_uuid);
+            }
+        }
+    }
+
+    #[test]
+    fn leaving_ends_the_partners_copy() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut alice_task = Task::new(Action {
+            partner_uuid: Some(bob),
+            ..Action::in_place("chat")
+        });
+        alice_task.state = TaskState::InProgress;
+        let mut bob_task = Task::new(partner_action(alice, alice_task.uuid()));
+        bob_task.state = TaskState::InProgress;
+        let mut bob_queue = VecDeque::from([Action::in_place("sit")]);
+
+        let alice_queue = VecDeque::new();
+        leave(
+            alice,
+            &alice_task,
+            &alice_queue,
+            bob,
+            &mut bob_task,
+            &mut bob_queue,
+        );
+        assert_eq!(bob_task.state(), TaskState::Done);
+        assert_eq!(bob_queue.len(), 1);
+    }
+
+    #[test]
+    fn leaving_forgets_deferred_invites() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut alice_task = Task::new(Action {
+            partner_uuid: Some(bob),
+            ..Action::in_place("chat")
+        });
+        alice_task.state = TaskState::Waiting;
+        // Bob said maybe later, and carries on sitting
+        let mut bob_task = Task::new(Action::in_place("sit"));
+        bob_task.state = TaskState::InProgress;
+        let mut bob_queue = VecDeque::from([partner_action(alice, alice_task.uuid())]);
+
+        let alice_queue = VecDeque::new();
+        leave(
+            alice,
+            &alice_task,
+            &alice_queue,
+            bob,
+            &mut bob_task,
+            &mut bob_queue,
+        );
+        assert_eq!(bob_task.state(), TaskState::InProgress);
+        assert!(bob_queue.is_empty());
+    }
+
+    #[test]
+    fn leaving_lets_down_whoever_waits_on_a_queued_copy() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut bob_task = Task::new(Action {
+            partner_uuid: Some(alice),
+            ..Action::in_place("chat")
+        });
+        bob_task.state = TaskState::Waiting;
+        // Alice said maybe later
+        let mut alice_task = Task::new(Action::in_place("sit"));
+        alice_task.state = TaskState::InProgress;
+        let alice_queue = VecDeque::from([partner_action(bob, bob_task.uuid())]);
+
+        let mut bob_queue = VecDeque::new();
+        leave(
+            alice,
+            &alice_task,
+            &alice_queue,
+            bob,
+            &mut bob_task,
+            &mut bob_queue,
+        );
+        assert_eq!(bob_task.state(), TaskState::Done);
     }
 }
@@ -4,10 +4,14 @@
 //!
 use godot::prelude::*;
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-use crate::{Action, ActionAdvertisement, Person, PersonNeeds};
+use crate::{
+    Action, ActionAdvertisement, ActionAdvertisementSource, ActionPriority, GroupRole, Person,
+    PersonNeeds, Relationship,
+};
 
 #[derive(Debug)]
 struct ActionTemp {
@@ -39,12 +43,23 @@ impl Ord for ActionTemp {
     }
 }
 
+/// How a person answers when someone asks them to do something together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalResponse {
+    Accept,
+    /// Maybe after finishing what they're doing.
+    Defer,
+    Reject,
+}
+
 #[derive(Debug)]
 pub struct PersonAi {
     /// Brain owner's UUID
     person_uuid: Uuid,
 
     pub last_action: String,
+    /// Own random numbers, so a seeded world plays out the same way every time.
+    rng: StdRng,
 }
 
 impl PersonAi {
@@ -53,52 +68,110 @@ impl PersonAi {
             person_uuid,
 
             last_action: "".into(),
+            rng: StdRng::from_os_rng(),
         }
     }
 
+    pub fn seed_rng(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// `people` must be in world order, for deterministic results. With `need`, only actions
+    /// mainly about that need are considered.
     pub fn decide_action(
-        &self,
+        &mut self,
         needs: &PersonNeeds,
         advertised_actions: &Vec<ActionAdvertisement>,
-        people: &HashMap<Uuid, Gd<Person>>,
+        people: &[(Uuid, Gd<Person>)],
+        relationships: &HashMap<Uuid, Relationship>,
         possible_actions: &[String],
+        need: Option<&str>,
     ) -> Action {
         let mut processed_actions = vec![];
 
         for advert in advertised_actions {
-            if !possible_actions.contains(&advert.action_key)
-                || advert.source_node.bind().is_reserved()
-            {
+            if !possible_actions.contains(&advert.action_key) {
                 continue;
             }
-
-            let mut potential_company: Vec<Gd<Person>> = vec![];
-            for (uuid, person) in people {
-                if *uuid == self.person_uuid {
-                    continue;
-                }
-                potential_company.push(person.clone());
+            if need.is_some() && Action::primary_need_of(&advert.action_key) != need {
+                continue;
             }
 
-            //if advert.required_people > potential_company.len() - 1 {
-            //    continue;
-            //}
-
-            let company = if advert.required_people == 2 {
-                Some(potential_company[0].bind().uuid())
-            } else {
-                None
+            let (company, group, relationship_score) = match &advert.source {
+                ActionAdvertisementSource::Furniture(furniture) => {
+                    if furniture.bind().is_reserved() {
+                        continue;
+                    }
+                    if advert.required_people > 2 {
+                        let Some((group, score)) = self.pick_group(
+                            &advert.action_key,
+                            vec![],
+                            advert.required_people,
+                            people,
+                            relationships,
+                        ) else {
+                            continue;
+                        };
+                        (None, Some(group), score)
+                    } else if advert.required_people == 2 {
+                        let Some((uuid, score)) =
+                            self.pick_partner(&advert.action_key, people, relationships)
+                        else {
+                            continue;
+                        };
+                        (Some(uuid), None, score)
+                    } else {
+                        (None, None, 0.0)
+                    }
+                }
+                ActionAdvertisementSource::Person(uuid) => {
+                    if *uuid == self.person_uuid {
+                        continue;
+                    }
+                    let Some((_, partner)) = people.iter().find(|(u, _)| u == uuid) else {
+                        continue;
+                    };
+                    if !partner.bind().is_available() {
+                        continue;
+                    }
+                    let relationship = relationships.get(uuid).copied().unwrap_or_default();
+                    if !relationship.allows(&advert.action_key) {
+                        continue;
+                    }
+                    if advert.required_people > 2 {
+                        let Some((group, score)) = self.pick_group(
+                            &advert.action_key,
+                            vec![*uuid],
+                            advert.required_people,
+                            people,
+                            relationships,
+                        ) else {
+                            continue;
+                        };
+                        (None, Some(group), score)
+                    } else {
+                        (
+                            Some(*uuid),
+                            None,
+                            relationship.action_score(&advert.action_key),
+                        )
+                    }
+                }
             };
 
             let action = Action {
                 key: advert.action_key.clone(),
-                object: Some(advert.source_node.clone()),
+                object: advert.furniture().cloned(),
                 partner_uuid: company,
                 master_uuid: None,
+                group,
+                priority: ActionPriority::Autonomous,
             };
 
             let mut score = self.score_action_by_needs(needs, advert);
             score += self.score_action_by_history(advert);
+            score += self.score_action_by_environment(advert);
+            score += relationship_score;
 
             processed_actions.push(ActionTemp { action, score });
         }
@@ -115,13 +188,128 @@ impl PersonAi {
             - 1
             - match processed_actions.len() {
                 1 => 0,
-                2 => rand::random_range(0..=1),
-                _ => rand::random_range(0..=2),
+                2 => self.rng.random_range(0..=1),
+                _ => self.rng.random_range(0..=2),
             };
 
         processed_actions[choice].action.clone()
     }
 
+    /// Should this person go along with `action` proposed by someone they feel `relationship` about.
+    /// `busy` means they're in the middle of something they can't drop right now.
+    pub fn evaluate_proposal(
+        &mut self,
+        action: &Action,
+        needs: &PersonNeeds,
+        mood: f64,
+        relationship: &Relationship,
+        busy: bool,
+    ) -> ProposalResponse {
+        const REJECT_THRESHOLD: f64 = 0.3;
+
+        if !relationship.allows(&action.key) {
+            return ProposalResponse::Reject;
+        }
+        if busy {
+            return ProposalResponse::Defer;
+        }
+
+        // Low need means high interest
+        let interest = action
+            .primary_need()
+            .and_then(|k| needs.get(k))
+            .map_or(0.5, |value| 1.0 - value);
+        let willingness = mood * 0.4
+            + relationship.friendship * 0.4
+            + interest * 0.2
+            + self.rng.random_range(-0.1..=0.1);
+
+        if willingness < REJECT_THRESHOLD {
+            ProposalResponse::Reject
+        } else {
+            ProposalResponse::Accept
+        }
+    }
+
+    /// Who would be the best company for this action, and how much they add to its score.
+    fn pick_partner(
+        &self,
+        action_key: &str,
+        people: &[(Uuid, Gd<Person>)],
+        relationships: &HashMap<Uuid, Relationship>,
+    ) -> Option<(Uuid, f64)> {
+        self.rank_company(action_key, people, relationships)
+            .into_iter()
+            .next()
+    }
+
+    /// Fill up to `slots - 1` member slots of a group led by the brain owner, starting with
+    /// `members`. Score is the average of what the members add. None if nobody can join.
+    fn pick_group(
+        &self,
+        action_key: &str,
+        mut members: Vec<Uuid>,
+        slots: usize,
+        people: &[(Uuid, Gd<Person>)],
+        relationships: &HashMap<Uuid, Relationship>,
+    ) -> Option<(GroupRole, f64)> {
+        let mut score: f64 = members
+            .iter()
+            .map(|uuid| {
+                relationships
+                    .get(uuid)
+                    .copied()
+                    .unwrap_or_default()
+                    .action_score(action_key)
+            })
+            .sum();
+        for (uuid, company_score) in self.rank_company(action_key, people, relationships) {
+            if members.len() + 1 >= slots {
+                break;
+            }
+            if !members.contains(&uuid) {
+                members.push(uuid);
+                score += company_score;
+            }
+        }
+
+        if members.is_empty() {
+            return None;
+        }
+        let score = score / members.len() as f64;
+        let joined = members.len();
+        Some((
+            GroupRole::Leader {
+                slots,
+                members,
+                joined,
+            },
+            score,
+        ))
+    }
+
+    /// Available people who'd be appropriate company for this action, best first.
+    /// Ties keep world order.
+    fn rank_company(
+        &self,
+        action_key: &str,
+        people: &[(Uuid, Gd<Person>)],
+        relationships: &HashMap<Uuid, Relationship>,
+    ) -> Vec<(Uuid, f64)> {
+        let mut ranked: Vec<(Uuid, f64)> = people
+            .iter()
+            .filter(|(uuid, person)| *uuid != self.person_uuid && person.bind().is_available())
+            .filter_map(|(uuid, _)| {
+                let relationship = relationships.get(uuid).copied().unwrap_or_default();
+                relationship
+                    .allows(action_key)
+                    .then(|| (*uuid, relationship.action_score(action_key)))
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+
     fn score_action_by_needs(&self, needs: &PersonNeeds, advert: &ActionAdvertisement) -> f64 {
         let mut score = 0.0;
 
@@ -143,6 +331,16 @@ impl PersonAi {
         score
     }
 
+    /// Nicer places are more inviting.
+    fn score_action_by_environment(&self, advert: &ActionAdvertisement) -> f64 {
+        const WEIGHT: f64 = 4.0;
+
+        match advert.furniture() {
+            Some(furniture) => (furniture.bind().environment() - 0.5) * WEIGHT,
+            None => 0.0,
+        }
+    }
+
     fn score_action_by_history(&self, advert: &ActionAdvertisement) -> f64 {
         if advert.action_key == self.last_action {
             return -100.0;
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: LGPL-3.0-or-later
+use serde::{Deserialize, Serialize};
+
 /// Real needs of a real human
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonNeeds {
     pub bladder: f64,
     pub comfort: f64,
@@ -68,6 +70,54 @@ impl PersonNeeds {
         self.social
     }
 
+    /// Value of a need by its string key.
+    pub fn get(&self, key: &str) -> Option<f64> {
+        match key {
+            "bladder" => Some(self.bladder),
+            "comfort" => Some(self.comfort),
+            "environment" => Some(self.environment),
+            "fun" => Some(self.fun),
+            "hunger" => Some(self.hunger),
+            "hygiene" => Some(self.hygiene),
+            "sleep" => Some(self.sleep),
+            "social" => Some(self.social),
+            _ => None,
+        }
+    }
+
+    /// The lowest need below `threshold`, if any. Environment never counts, it follows the
+    /// surroundings and no action is about it.
+    pub fn most_urgent(&self, threshold: f64) -> Option<&'static str> {
+        [
+            ("bladder", self.bladder),
+            ("comfort", self.comfort),
+            ("fun", self.fun),
+            ("hunger", self.hunger),
+            ("hygiene", self.hygiene),
+            ("sleep", self.sleep),
+            ("social", self.social),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value < threshold)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(key, _)| key)
+    }
+
+    /// 0.0..=1.0. How good life is overall, before moodlets.
+    pub fn mood(&self) -> f64 {
+        let needs = [
+            self.bladder,
+            self.comfort,
+            self.environment,
+            self.fun,
+            self.hunger,
+            self.hygiene,
+            self.sleep,
+            self.social,
+        ];
+        needs.iter().sum::<f64>() / needs.len() as f64
+    }
+
     pub fn set_bladder(&mut self, value: f64) {
         self.bladder = value.clamp(0.0, 1.0)
     }
@@ -100,14 +150,15 @@ impl PersonNeeds {
         self.social = value.clamp(0.0, 1.0)
     }
 
-    pub fn update(&mut self, delta: f64) {
-        self.bladder -= Self::NEED_BLADDER_DECAY_RATE * delta;
-        self.comfort -= Self::NEED_COMFORT_DECAY_RATE * delta;
-        self.fun -= Self::NEED_FUN_DECAY_RATE * delta;
-        self.hunger -= Self::NEED_HUNGER_DECAY_RATE * delta;
-        self.hygiene -= Self::NEED_HYGIENE_DECAY_RATE * delta;
-        self.sleep -= Self::NEED_SLEEP_DECAY_RATE * delta;
-        self.social -= Self::NEED_SOCIAL_DECAY_RATE * delta;
+    /// Decay needs. `decay` scales the base rates, see [NeedDecay].
+    pub fn update(&mut self, delta: f64, decay: &NeedDecay) {
+        self.bladder -= Self::NEED_BLADDER_DECAY_RATE * decay.bladder * delta;
+        self.comfort -= Self::NEED_COMFORT_DECAY_RATE * decay.comfort * delta;
+        self.fun -= Self::NEED_FUN_DECAY_RATE * decay.fun * delta;
+        self.hunger -= Self::NEED_HUNGER_DECAY_RATE * decay.hunger * delta;
+        self.hygiene -= Self::NEED_HYGIENE_DECAY_RATE * decay.hygiene * delta;
+        self.sleep -= Self::NEED_SLEEP_DECAY_RATE * decay.sleep * delta;
+        self.social -= Self::NEED_SOCIAL_DECAY_RATE * decay.social * delta;
 
         self.bladder = self.bladder.clamp(0.0, 1.0);
         self.comfort = self.comfort.clamp(0.0, 1.0);
@@ -118,3 +169,122 @@ impl PersonNeeds {
         self.social = self.social.clamp(0.0, 1.0);
     }
 }
+
+/// Need decay rate multipliers. 1.0 is the base rate, 0.0 pauses decay.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NeedDecay {
+    pub bladder: f64,
+    pub comfort: f64,
+    pub fun: f64,
+    pub hunger: f64,
+    pub hygiene: f64,
+    pub sleep: f64,
+    pub social: f64,
+}
+
+impl Default for NeedDecay {
+    fn default() -> Self {
+        Self {
+            bladder: 1.0,
+            comfort: 1.0,
+            fun: 1.0,
+            hunger: 1.0,
+            hygiene: 1.0,
+            sleep: 1.0,
+            social: 1.0,
+        }
+    }
+}
+
+impl NeedDecay {
+    /// Stack two sets of multipliers on top of each other.
+    pub fn combined(&self, other: &Self) -> Self {
+        Self {
+            bladder: self.bladder * other.bladder,
+            comfort: self.comfort * other.comfort,
+            fun: self.fun * other.fun,
+            hunger: self.hunger * other.hunger,
+            hygiene: self.hygiene * other.hygiene,
+            sleep: self.sleep * other.sleep,
+            social: self.social * other.social,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnvironmentScore;
+
+    #[test]
+    fn needs_decay_over_time() {
+        let mut needs = PersonNeeds::default();
+        needs.update(10.0, &NeedDecay::default());
+
+        assert!((needs.hunger() - 0.9).abs() < 1e-9);
+        assert!((needs.social() - 0.9).abs() < 1e-9);
+        // Environment follows the surroundings instead
+        assert_eq!(needs.environment(), 1.0);
+    }
+
+    #[test]
+    fn zero_multiplier_pauses_decay() {
+        let mut needs = PersonNeeds::default();
+        let decay = NeedDecay {
+            sleep: 0.0,
+            ..Default::default()
+        };
+        needs.update(10.0, &decay);
+
+        assert_eq!(needs.sleep(), 1.0);
+        assert!(needs.fun() < 1.0);
+    }
+
+    #[test]
+    fn multipliers_combine_by_product() {
+        let a = NeedDecay {
+            fun: 1.5,
+            sleep: 0.5,
+            ..Default::default()
+        };
+        let b = NeedDecay {
+            fun: 2.0,
+            hunger: 0.0,
+            ..Default::default()
+        };
+        let combined = a.combined(&b);
+
+        assert_eq!(combined.fun, 3.0);
+        assert_eq!(combined.sleep, 0.5);
+        assert_eq!(combined.hunger, 0.0);
+        assert_eq!(combined.social, 1.0);
+        assert_eq!(combined, b.combined(&a));
+    }
+
+    #[test]
+    fn needs_stay_within_bounds() {
+        let mut needs = PersonNeeds::default();
+        needs.update(1000.0, &NeedDecay::default());
+        assert_eq!(needs.bladder(), 0.0);
+        assert_eq!(needs.hunger(), 0.0);
+
+        needs.set_fun(1.5);
+        needs.set_comfort(-0.5);
+        assert_eq!(needs.fun(), 1.0);
+        assert_eq!(needs.comfort(), 0.0);
+    }
+
+    #[test]
+    fn dark_room_is_not_urgent() {
+        // Pitch black, with a puddle on the floor
+        let dark_room = EnvironmentScore::new([-0.3], 0.0, Some(12), 0.0);
+        let mut needs = PersonNeeds::default();
+        needs.set_environment(dark_room.total());
+
+        assert!(needs.environment() < 0.1);
+        assert_eq!(needs.most_urgent(0.15), None);
+
+        needs.set_fun(0.12);
+        assert_eq!(needs.most_urgent(0.15), Some("fun"));
+    }
+}
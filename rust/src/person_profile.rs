@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use godot::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::NeedDecay;
+
+const FIRST_NAMES: [&str; 12] = [
+    "Alice", "Bob", "Carol", "Dave", "Erin", "Frank", "Grace", "Heidi", "Ivan", "Judy", "Mallory",
+    "Oscar",
+];
+const LAST_NAMES: [&str; 8] = [
+    "Smith",
+    "Jones",
+    "Miller",
+    "Garcia",
+    "Kowalski",
+    "Nguyen",
+    "Okafor",
+    "Lindqvist",
+];
+const SKIN_COLORS: [[f32; 3]; 5] = [
+    [1.0, 0.87, 0.77],
+    [0.94, 0.76, 0.6],
+    [0.78, 0.58, 0.42],
+    [0.55, 0.38, 0.26],
+    [0.36, 0.24, 0.17],
+];
+const HAIR_COLORS: [[f32; 3]; 5] = [
+    [0.1, 0.08, 0.06],
+    [0.35, 0.22, 0.12],
+    [0.85, 0.7, 0.4],
+    [0.6, 0.2, 0.08],
+    [0.75, 0.75, 0.75],
+];
+
+/// Stage of life, mostly affects size for now.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgeStage {
+    Child,
+    Teen,
+    #[default]
+    Adult,
+    Elder,
+}
+
+impl AgeStage {
+    pub const ALL: [AgeStage; 4] = [
+        AgeStage::Child,
+        AgeStage::Teen,
+        AgeStage::Adult,
+        AgeStage::Elder,
+    ];
+
+    pub fn to_label(self) -> &'static str {
+        match self {
+            AgeStage::Child => "Child",
+            AgeStage::Teen => "Teen",
+            AgeStage::Adult => "Adult",
+            AgeStage::Elder => "Elder",
+        }
+    }
+
+    /// Model scale relative to an adult.
+    pub fn scale(self) -> f32 {
+        match self {
+            AgeStage::Child => 0.6,
+            AgeStage::Teen => 0.85,
+            AgeStage::Adult => 1.0,
+            AgeStage::Elder => 0.95,
+        }
+    }
+}
+
+/// Personality quirk, changes how fast some needs decay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersonTrait {
+    Outgoing,
+    Loner,
+    Neat,
+    Slob,
+    Playful,
+    Lazy,
+}
+
+impl PersonTrait {
+    pub const ALL: [PersonTrait; 6] = [
+        PersonTrait::Outgoing,
+        PersonTrait::Loner,
+        PersonTrait::Neat,
+        PersonTrait::Slob,
+        PersonTrait::Playful,
+        PersonTrait::Lazy,
+    ];
+
+    pub fn to_label(self) -> &'static str {
+        match self {
+            PersonTrait::Outgoing => "Outgoing",
+            PersonTrait::Loner => "Loner",
+            PersonTrait::Neat => "Neat",
+            PersonTrait::Slob => "Slob",
+            PersonTrait::Playful => "Playful",
+            PersonTrait::Lazy => "Lazy",
+        }
+    }
+
+    /// Traits that can't be picked together.
+    pub fn conflicts_with(self, other: PersonTrait) -> bool {
+        matches!(
+            (self, other),
+            (PersonTrait::Outgoing, PersonTrait::Loner)
+                | (PersonTrait::Loner, PersonTrait::Outgoing)
+                | (PersonTrait::Neat, PersonTrait::Slob)
+                | (PersonTrait::Slob, PersonTrait::Neat)
+        )
+    }
+
+    pub fn need_decay(self) -> NeedDecay {
+        match self {
+            PersonTrait::Outgoing => NeedDecay {
+                social: 1.5,
+                ..Default::default()
+            },
+            PersonTrait::Loner => NeedDecay {
+                social: 0.5,
+                ..Default::default()
+            },
+            PersonTrait::Neat => NeedDecay {
+                hygiene: 1.3,
+                ..Default::default()
+            },
+            PersonTrait::Slob => NeedDecay {
+                hygiene: 0.7,
+                ..Default::default()
+            },
+            PersonTrait::Playful => NeedDecay {
+                fun: 1.5,
+                ..Default::default()
+            },
+            PersonTrait::Lazy => NeedDecay {
+                comfort: 1.3,
+                sleep: 1.2,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Who a [crate::Person] is and what they look like.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonProfile {
+    pub first_name: String,
+    pub last_name: String,
+    pub age_stage: AgeStage,
+    /// Height multiplier, 0.9..=1.1
+    pub height: f32,
+    /// Width multiplier, 0.85..=1.15
+    pub build: f32,
+    pub skin_color: [f32; 3],
+    pub hair_color: [f32; 3],
+    pub traits: Vec<PersonTrait>,
+}
+
+impl Default for PersonProfile {
+    fn default() -> Self {
+        Self {
+            first_name: "New".into(),
+            last_name: "Person".into(),
+            age_stage: AgeStage::default(),
+            height: 1.0,
+            build: 1.0,
+            skin_color: SKIN_COLORS[0],
+            hair_color: HAIR_COLORS[0],
+            traits: vec![],
+        }
+    }
+}
+
+impl PersonProfile {
+    pub const MAX_TRAITS: usize = 3;
+
+    pub fn new(first_name: &str, last_name: &str) -> Self {
+        Self {
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Someone made up on the spot, like a visitor.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let mut profile = Self {
+            first_name: FIRST_NAMES[rng.random_range(0..FIRST_NAMES.len())].into(),
+            last_name: LAST_NAMES[rng.random_range(0..LAST_NAMES.len())].into(),
+            age_stage: AgeStage::ALL[rng.random_range(0..AgeStage::ALL.len())],
+            height: rng.random_range(0.9..=1.1),
+            build: rng.random_range(0.85..=1.15),
+            skin_color: SKIN_COLORS[rng.random_range(0..SKIN_COLORS.len())],
+            hair_color: HAIR_COLORS[rng.random_range(0..HAIR_COLORS.len())],
+            traits: vec![],
+        };
+        for _ in 0..rng.random_range(0..=Self::MAX_TRAITS) {
+            let candidate = PersonTrait::ALL[rng.random_range(0..PersonTrait::ALL.len())];
+            if profile.can_add_trait(candidate) {
+                profile.traits.push(candidate);
+            }
+        }
+        profile
+    }
+
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+    }
+
+    pub fn skin_color(&self) -> Color {
+        let [r, g, b] = self.skin_color;
+        Color::from_rgb(r, g, b)
+    }
+
+    pub fn hair_color(&self) -> Color {
+        let [r, g, b] = self.hair_color;
+        Color::from_rgb(r, g, b)
+    }
+
+    /// Model scale from age, height and build.
+    pub fn body_scale(&self) -> Vector3 {
+        let age = self.age_stage.scale();
+        Vector3::new(self.build, self.height, self.build) * age
+    }
+
+    /// Can `new_trait` be added next to the current ones.
+    pub fn can_add_trait(&self, new_trait: PersonTrait) -> bool {
+        self.traits.len() < Self::MAX_TRAITS
+            && !self
+                .traits
+                .iter()
+                .any(|t| *t == new_trait || t.conflicts_with(new_trait))
+    }
+
+    /// Is this a person that could exist.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.first_name.trim().is_empty() || self.last_name.trim().is_empty() {
+            return Err("Needs a first and last name".into());
+        }
+        if self.traits.len() > Self::MAX_TRAITS {
+            return Err(format!("At most {} traits", Self::MAX_TRAITS));
+        }
+        for (i, a) in self.traits.iter().enumerate() {
+            for b in &self.traits[i + 1..] {
+                if a == b || a.conflicts_with(*b) {
+                    return Err(format!(
+                        "{} and {} don't go together",
+                        a.to_label(),
+                        b.to_label()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Need decay multipliers from all traits.
+    pub fn need_decay(&self) -> NeedDecay {
+        self.traits.iter().fold(NeedDecay::default(), |decay, t| {
+            decay.combined(&t.need_decay())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn default_profile_is_valid() {
+        assert_eq!(PersonProfile::new("Alice", "Smith").validate(), Ok(()));
+    }
+
+    #[test]
+    fn names_are_required() {
+        assert!(PersonProfile::new("", "Smith").validate().is_err());
+        assert!(PersonProfile::new("Alice", "  ").validate().is_err());
+    }
+
+    #[test]
+    fn too_many_traits() {
+        let profile = PersonProfile {
+            traits: vec![
+                PersonTrait::Outgoing,
+                PersonTrait::Neat,
+                PersonTrait::Playful,
+                PersonTrait::Lazy,
+            ],
+            ..PersonProfile::new("Alice", "Smith")
+        };
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn conflicting_or_repeated_traits() {
+        let conflicting = PersonProfile {
+            traits: vec![PersonTrait::Neat, PersonTrait::Slob],
+            ..PersonProfile::new("Alice", "Smith")
+        };
+        assert!(conflicting.validate().is_err());
+
+        let repeated = PersonProfile {
+            traits: vec![PersonTrait::Lazy, PersonTrait::Lazy],
+            ..PersonProfile::new("Alice", "Smith")
+        };
+        assert!(repeated.validate().is_err());
+    }
+
+    #[test]
+    fn random_profiles_are_valid() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            assert_eq!(PersonProfile::random(&mut rng).validate(), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use serde::{Deserialize, Serialize};
+
+/// How one person feels about another. Not necessarily mutual.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Relationship {
+    /// -1.0..=1.0
+    pub friendship: f64,
+    /// 0.0..=1.0
+    pub romance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationshipLabel {
+    Enemy,
+    Acquaintance,
+    Friend,
+    Partner,
+}
+
+impl RelationshipLabel {
+    pub fn to_label(self) -> &'static str {
+        match self {
+            RelationshipLabel::Enemy => "Enemy",
+            RelationshipLabel::Acquaintance => "Acquaintance",
+            RelationshipLabel::Friend => "Friend",
+            RelationshipLabel::Partner => "Partner",
+        }
+    }
+}
+
+impl Relationship {
+    /// Per second, towards neutral.
+    const DECAY_RATE: f64 = 0.001;
+
+    pub fn label(&self) -> RelationshipLabel {
+        if self.friendship <= -0.3 {
+            RelationshipLabel::Enemy
+        } else if self.romance >= 0.5 && self.friendship >= 0.3 {
+            RelationshipLabel::Partner
+        } else if self.friendship >= 0.4 {
+            RelationshipLabel::Friend
+        } else {
+            RelationshipLabel::Acquaintance
+        }
+    }
+
+    /// Would this action be appropriate in this relationship.
+    pub fn allows(&self, action_key: &str) -> bool {
+        match action_key {
+            "hug" => self.friendship >= 0.2 || self.romance >= 0.2,
+            "do_the_mario" => self.label() == RelationshipLabel::Partner,
+            _ => true,
+        }
+    }
+
+    /// How much this makes someone want to do the action with the other person.
+    pub fn action_score(&self, action_key: &str) -> f64 {
+        match action_key {
+            "argue" => -self.friendship * 10.0,
+            "hug" | "do_the_mario" => (self.friendship + self.romance * 2.0) * 10.0,
+            _ => self.friendship * 10.0,
+        }
+    }
+
+    /// Effect of doing an action together for `delta` seconds.
+    pub fn apply_action(&mut self, action_key: &str, delta: f64) {
+        let (friendship, romance) = match action_key {
+            "chat" => (0.02, 0.0),
+            "group_chat" => (0.015, 0.0),
+            "eat_together" => (0.01, 0.0),
+            "joke" => (0.03, 0.0),
+            "hug" => (0.01, 0.03),
+            "argue" => (-0.04, -0.02),
+            "do_the_mario" => (0.01, 0.04),
+            _ => (0.0, 0.0),
+        };
+        self.friendship = (self.friendship + friendship * delta).clamp(-1.0, 1.0);
+        self.romance = (self.romance + romance * delta).clamp(0.0, 1.0);
+    }
+
+    /// Slowly drift apart when not interacting.
+    pub fn decay(&mut self, delta: f64) {
+        let step = Self::DECAY_RATE * delta;
+        self.friendship -= self.friendship.clamp(-step, step);
+        self.romance -= self.romance.clamp(0.0, step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relationship(friendship: f64, romance: f64) -> Relationship {
+        Relationship {
+            friendship,
+            romance,
+        }
+    }
+
+    #[test]
+    fn labels_by_threshold() {
+        assert_eq!(
+            relationship(0.0, 0.0).label(),
+            RelationshipLabel::Acquaintance
+        );
+        assert_eq!(relationship(-0.3, 0.0).label(), RelationshipLabel::Enemy);
+        assert_eq!(
+            relationship(-0.29, 0.0).label(),
+            RelationshipLabel::Acquaintance
+        );
+        assert_eq!(
+            relationship(0.39, 0.0).label(),
+            RelationshipLabel::Acquaintance
+        );
+        assert_eq!(relationship(0.4, 0.0).label(), RelationshipLabel::Friend);
+        assert_eq!(relationship(0.3, 0.5).label(), RelationshipLabel::Partner);
+        assert_eq!(
+            relationship(0.29, 0.5).label(),
+            RelationshipLabel::Acquaintance
+        );
+        // Romance doesn't save an enemy
+        assert_eq!(relationship(-0.5, 1.0).label(), RelationshipLabel::Enemy);
+    }
+
+    #[test]
+    fn decays_towards_neutral() {
+        let mut good = relationship(0.5, 0.5);
+        good.decay(100.0);
+        assert!((good.friendship - 0.4).abs() < 1e-9);
+        assert!((good.romance - 0.4).abs() < 1e-9);
+
+        let mut bad = relationship(-0.5, 0.0);
+        bad.decay(100.0);
+        assert!((bad.friendship + 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decay_stops_at_neutral() {
+        let mut small = relationship(0.05, 0.05);
+        small.decay(1000.0);
+        assert_eq!(small, Relationship::default());
+
+        let mut small_bad = relationship(-0.05, 0.0);
+        small_bad.decay(1000.0);
+        assert_eq!(small_bad.friendship, 0.0);
+    }
+
+    #[test]
+    fn actions_stay_in_range() {
+        let mut r = relationship(0.99, 0.99);
+        r.apply_action("hug", 100.0);
+        assert_eq!(r.friendship, 1.0);
+        assert_eq!(r.romance, 1.0);
+
+        r.apply_action("argue", 1000.0);
+        assert_eq!(r.friendship, -1.0);
+        assert_eq!(r.romance, 0.0);
+    }
+}
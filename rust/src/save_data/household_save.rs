@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdSave {
+    pub name: String,
+    pub members: Vec<Uuid>,
+    pub funds: i64,
+    pub lot: Option<String>,
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+
+//! Save Data module
+//!
+//! Serializable snapshots of game state.
+//! No Godot Nodes within this module.
+//!
+
+mod household_save;
+mod person_save;
+mod world_save;
+
+pub use household_save::HouseholdSave;
+pub use person_save::PersonSave;
+pub use world_save::WorldSave;
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Autonomy, Buff, PersonNeeds, PersonProfile, Relationship};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonSave {
+    pub uuid: Uuid,
+    /// For display, names aren't unique
+    pub name: String,
+    pub position: [f32; 3],
+    pub needs: PersonNeeds,
+    /// None means the world default is used.
+    pub autonomy: Option<Autonomy>,
+    pub profile: PersonProfile,
+    pub buffs: Vec<Buff>,
+    /// By uuid of the other person
+    pub relationships: HashMap<Uuid, Relationship>,
+}
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{HouseholdSave, PersonSave};
+use crate::{Autonomy, CameraBookmarks, EventScheduler, Weather};
+
+/// Everything about the world worth keeping.
+///
+/// What people are doing or have queued up isn't saved, everyone picks something new on load.
+/// Furniture other than puddles and the switch of lamps comes from the lot as it's built.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldSave {
+    /// Seconds since midnight
+    pub time_of_day: f64,
+    /// Days since the start
+    pub day: u64,
+    /// Game minutes per real second at regular speed
+    pub minutes_per_second: f64,
+    pub schedule: EventScheduler,
+    pub weather: Weather,
+    pub autonomy: Autonomy,
+    pub people: Vec<PersonSave>,
+    pub households: Vec<HouseholdSave>,
+    /// Name of the playable household
+    pub active_household: Option<String>,
+    /// Wall spans on the lot grid
+    pub walls: Vec<[[i32; 2]; 2]>,
+    /// Positions of messes on the floor
+    pub puddles: Vec<[f32; 3]>,
+    /// Positions of lamps that are on
+    pub lit_lamps: Vec<[f32; 3]>,
+    /// Saved camera views, by lot name
+    pub camera_bookmarks: HashMap<String, CameraBookmarks>,
+}
+
+impl WorldSave {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+}
@@ -41,7 +41,7 @@ impl INode3D for SpiritLevel {
             self.base_mut().show();
 
             let position = target.get_global_position() + Vector3::UP * Y_OFFSET;
-            let mood = target.bind().needs().sleep() as f32;
+            let mood = target.bind().mood() as f32;
 
             self.base_mut().set_global_position(position);
 
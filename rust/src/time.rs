@@ -1,17 +1,330 @@
 // SPDX-License-Identifier: LGPL-3.0-or-later
-#[derive(Debug)]
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimeScale {
     Regular,
     Fast,
     Superfast,
+    /// Skip ahead as fast as possible until something happens, see [WakeReason].
+    Ultra,
 }
 
 impl TimeScale {
-    pub fn to_engine_time(&self) -> f64 {
+    /// Simulated seconds per real second.
+    pub fn speed(&self) -> f64 {
         match self {
             TimeScale::Regular => 1.0,
             TimeScale::Fast => 5.0,
             TimeScale::Superfast => 10.0,
+            TimeScale::Ultra => 2400.0,
+        }
+    }
+
+    /// Simulation ticks allowed per rendered frame before falling behind.
+    pub fn max_steps_per_frame(&self) -> u32 {
+        match self {
+            TimeScale::Ultra => 1200,
+            _ => 30,
+        }
+    }
+}
+
+/// Why [TimeScale::Ultra] stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WakeReason {
+    Morning,
+    /// Someone playable is about to have a bad time
+    CriticalNeed {
+        name: String,
+        need: &'static str,
+    },
+    /// Key of the event that fired
+    ScheduledEvent(String),
+    /// Clicked or changed speed
+    Player,
+}
+
+impl WakeReason {
+    pub fn to_label(&self) -> String {
+        match self {
+            WakeReason::Morning => "Good morning!".into(),
+            WakeReason::CriticalNeed { name, need } => format!("{name} needs attention: {need}"),
+            WakeReason::ScheduledEvent(key) => format!("Scheduled: {key}"),
+            WakeReason::Player => "Stopped by player".into(),
+        }
+    }
+}
+
+const MINUTES_PER_HOUR: u64 = 60;
+const MINUTES_PER_DAY: u64 = 60 * 24;
+const DAYS_PER_WEEK: u64 = 7;
+/// Two weeks per season, eight weeks a year.
+const DAYS_PER_SEASON: u64 = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    pub fn to_short_label(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
+            Weekday::Sunday => "Sun",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [
+        Season::Spring,
+        Season::Summer,
+        Season::Autumn,
+        Season::Winter,
+    ];
+
+    pub fn to_label(self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+/// Boundaries crossed during one [GameClock::advance].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClockTick {
+    /// Hours since day 0 that started, in order
+    pub hours: Vec<u64>,
+}
+
+impl ClockTick {
+    /// Days that started, in order.
+    pub fn days(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hours
+            .iter()
+            .filter(|hour| *hour % 24 == 0)
+            .map(|hour| hour / 24)
+    }
+}
+
+/// Game calendar. Day 0 is the first Monday of spring.
+#[derive(Debug, Clone)]
+pub struct GameClock {
+    /// Game minutes since midnight of day 0
+    minutes: f64,
+    /// Game minutes per second of (engine scaled) real time
+    minutes_per_second: f64,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            minutes: 0.0,
+            minutes_per_second: Self::DEFAULT_MINUTES_PER_SECOND,
         }
     }
 }
+
+impl GameClock {
+    /// 1 sec realtime => 1 min game time
+    pub const DEFAULT_MINUTES_PER_SECOND: f64 = 1.0;
+
+    pub fn new(day: u64, time_of_day: Duration) -> Self {
+        let mut clock = Self::default();
+        clock.set_time(day, time_of_day);
+        clock
+    }
+
+    /// Clock stopped at `minutes` since midnight of day 0, for asking about a moment in time.
+    pub fn from_total_minutes(minutes: f64) -> Self {
+        Self {
+            minutes: minutes.max(0.0),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_time(&mut self, day: u64, time_of_day: Duration) {
+        let time_of_day = time_of_day.as_secs_f64() / 60.0;
+        self.minutes = (day * MINUTES_PER_DAY) as f64 + time_of_day.min(MINUTES_PER_DAY as f64);
+    }
+
+    pub fn minutes_per_second(&self) -> f64 {
+        self.minutes_per_second
+    }
+
+    pub fn set_minutes_per_second(&mut self, minutes_per_second: f64) {
+        self.minutes_per_second = minutes_per_second.max(0.0);
+    }
+
+    /// Move time forward by `delta` real seconds.
+    pub fn advance(&mut self, delta: f64) -> ClockTick {
+        self.advance_minutes(delta * self.minutes_per_second)
+    }
+
+    /// Move time forward by game minutes, reporting every hour and day started on the way.
+    pub fn advance_minutes(&mut self, minutes: f64) -> ClockTick {
+        let hour_before = self.total_hours();
+        self.minutes += minutes.max(0.0);
+
+        ClockTick {
+            hours: (hour_before + 1..=self.total_hours()).collect(),
+        }
+    }
+
+    /// Game minutes since midnight of day 0.
+    pub fn total_minutes(&self) -> f64 {
+        self.minutes
+    }
+
+    fn total_hours(&self) -> u64 {
+        self.minutes as u64 / MINUTES_PER_HOUR
+    }
+
+    /// Days since the start, counting from 0.
+    pub fn day(&self) -> u64 {
+        self.minutes as u64 / MINUTES_PER_DAY
+    }
+
+    pub fn time_of_day(&self) -> Duration {
+        let minutes = self.minutes - (self.day() * MINUTES_PER_DAY) as f64;
+        Duration::from_secs_f64(minutes * 60.0)
+    }
+
+    pub fn hour(&self) -> u64 {
+        self.total_hours() % 24
+    }
+
+    pub fn minute(&self) -> u64 {
+        self.minutes as u64 % MINUTES_PER_HOUR
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        Weekday::ALL[(self.day() % DAYS_PER_WEEK) as usize]
+    }
+
+    /// Weeks since the start, counting from 0.
+    pub fn week(&self) -> u64 {
+        self.day() / DAYS_PER_WEEK
+    }
+
+    pub fn season(&self) -> Season {
+        Season::ALL[(self.day() / DAYS_PER_SEASON % Season::ALL.len() as u64) as usize]
+    }
+
+    /// Day within the current season, counting from 1.
+    pub fn day_of_season(&self) -> u64 {
+        self.day() % DAYS_PER_SEASON + 1
+    }
+
+    /// Like "Mon 08:30".
+    pub fn to_time_label(&self) -> String {
+        format!(
+            "{} {:02}:{:02}",
+            self.weekday().to_short_label(),
+            self.hour(),
+            self.minute()
+        )
+    }
+
+    /// Like "Spring, day 3".
+    pub fn to_date_label(&self) -> String {
+        format!("{}, day {}", self.season().to_label(), self.day_of_season())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock_at(day: u64, hour: u64, minute: u64) -> GameClock {
+        GameClock::new(day, Duration::from_secs((hour * 60 + minute) * 60))
+    }
+
+    #[test]
+    fn advance_across_midnight() {
+        let mut clock = clock_at(0, 23, 30);
+        let tick = clock.advance_minutes(45.0);
+
+        assert_eq!(clock.day(), 1);
+        assert_eq!((clock.hour(), clock.minute()), (0, 15));
+        assert_eq!(tick.hours, vec![24]);
+        assert_eq!(tick.days().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn big_step_reports_every_boundary() {
+        let mut clock = clock_at(0, 22, 0);
+        let tick = clock.advance_minutes(60.0 * 27.0);
+
+        assert_eq!(tick.hours, (23..=49).collect::<Vec<_>>());
+        assert_eq!(tick.days().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn small_step_reports_nothing() {
+        let mut clock = clock_at(0, 8, 10);
+        let tick = clock.advance(30.0);
+
+        assert_eq!(clock.minute(), 40);
+        assert_eq!(tick, ClockTick::default());
+    }
+
+    #[test]
+    fn weekday_rolls_over() {
+        assert_eq!(clock_at(0, 12, 0).weekday(), Weekday::Monday);
+        assert_eq!(clock_at(6, 12, 0).weekday(), Weekday::Sunday);
+
+        let mut clock = clock_at(6, 23, 59);
+        clock.advance_minutes(1.0);
+        assert_eq!(clock.weekday(), Weekday::Monday);
+        assert_eq!(clock.week(), 1);
+    }
+
+    #[test]
+    fn season_rolls_over() {
+        let mut clock = clock_at(13, 23, 0);
+        assert_eq!(clock.season(), Season::Spring);
+        assert_eq!(clock.day_of_season(), 14);
+
+        clock.advance_minutes(60.0);
+        assert_eq!(clock.season(), Season::Summer);
+        assert_eq!(clock.day_of_season(), 1);
+
+        // A year is four seasons, then spring again
+        assert_eq!(clock_at(14 * 3, 0, 0).season(), Season::Winter);
+        assert_eq!(clock_at(14 * 4, 0, 0).season(), Season::Spring);
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+//! Class: [UiActionQueue]
+//! Desc: Shows what a person is doing and going to do. Click an entry to cancel it.
+//!
+use godot::classes::{Button, HBoxContainer, IMarginContainer, MarginContainer};
+use godot::prelude::*;
+
+use crate::Person;
+
+const ENTRY_W: f32 = 96.0;
+
+#[derive(Debug, GodotClass)]
+#[class(base=MarginContainer)]
+pub struct UiActionQueue {
+    selected_person: Option<Gd<Person>>,
+    /// What the buttons currently show, to avoid rebuilding them every frame.
+    shown_entries: Vec<(String, bool)>,
+
+    hbox: Gd<HBoxContainer>,
+    buttons: Vec<Gd<Button>>,
+
+    base: Base<MarginContainer>,
+}
+
+#[godot_api]
+impl IMarginContainer for UiActionQueue {
+    fn init(base: Base<Self::Base>) -> Self {
+        Self {
+            selected_person: None,
+            shown_entries: vec![],
+
+            hbox: HBoxContainer::new_alloc(),
+            buttons: vec![],
+
+            base,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.setup_ui();
+    }
+
+    fn process(&mut self, _delta: f64) {
+        self.show_queue();
+    }
+}
+
+#[godot_api]
+impl UiActionQueue {
+    /// Index 0 is the current task, the rest are queued actions.
+    #[func]
+    fn on_entry_pressed(&mut self, index: i64) {
+        let Some(person) = &mut self.selected_person else {
+            return;
+        };
+        let mut person = person.bind_mut();
+        if index == 0 {
+            person.cancel_task();
+        } else {
+            person.cancel_queued(index as usize - 1);
+        }
+    }
+}
+
+impl UiActionQueue {
+    fn setup_ui(&mut self) {
+        let mut hbox = self.hbox.clone();
+        hbox.set_name("hbox");
+
+        self.base_mut().add_child(&hbox);
+    }
+
+    pub fn select_person(&mut self, person: Option<Gd<Person>>) {
+        self.selected_person = person;
+    }
+
+    fn show_queue(&mut self) {
+        let entries: Vec<(String, bool)> = match &self.selected_person {
+            Some(person) => {
+                let person = person.bind();
+                let current = person.task().action();
+                let mut entries = vec![(current.to_present_tense(), current.can_be_cancelled())];
+                entries.extend(
+                    person
+                        .action_queue()
+                        .iter()
+                        .map(|a| (a.to_menu_label(), a.can_be_cancelled())),
+                );
+                entries
+            }
+            None => vec![],
+        };
+
+        if entries == self.shown_entries {
+            return;
+        }
+
+        for mut button in self.buttons.drain(..) {
+            button.queue_free();
+        }
+
+        let this_gd = self.to_gd();
+        for (i, (label, cancellable)) in entries.iter().enumerate() {
+            let mut button = Button::new_alloc();
+            button.set_text(label);
+            button.set_custom_minimum_size(Vector2::new(ENTRY_W, 0.0));
+            button.set_disabled(!cancellable);
+            button.set_tooltip_text(if *cancellable {
+                "Click to cancel"
+            } else {
+                "Can't be cancelled"
+            });
+            button.connect(
+                "pressed",
+                &this_gd
+                    .callable("on_entry_pressed")
+                    .bindv(&varray![i as i64]),
+            );
+            button.set_name(format!("button_{i}").as_str());
+
+            self.hbox.add_child(&button);
+            self.buttons.push(button);
+        }
+
+        self.shown_entries = entries;
+    }
+}
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+//! Class: [UiCharacterCreator]
+//! Desc: A window for making up a new person.
+//!
+use godot::classes::{
+    control::{LayoutPreset, SizeFlags},
+    Button, CheckBox, ColorPickerButton, Control, GridContainer, HBoxContainer, HSlider,
+    IPanelContainer, Label, LineEdit, OptionButton, PanelContainer, VBoxContainer,
+};
+use godot::prelude::*;
+
+use crate::{AgeStage, PersonProfile, PersonTrait};
+
+const MIN_W: f32 = 320.0;
+
+#[derive(Debug, GodotClass)]
+#[class(base=PanelContainer)]
+pub struct UiCharacterCreator {
+    /// Last profile that passed validation on create
+    profile: PersonProfile,
+
+    edit_first_name: Gd<LineEdit>,
+    edit_last_name: Gd<LineEdit>,
+    opt_age_stage: Gd<OptionButton>,
+    slider_height: Gd<HSlider>,
+    slider_build: Gd<HSlider>,
+    pick_skin_color: Gd<ColorPickerButton>,
+    pick_hair_color: Gd<ColorPickerButton>,
+    check_traits: Vec<Gd<CheckBox>>,
+    lab_error: Gd<Label>,
+
+    base: Base<PanelContainer>,
+}
+
+#[godot_api]
+impl IPanelContainer for UiCharacterCreator {
+    fn init(base: Base<Self::Base>) -> Self {
+        Self {
+            profile: PersonProfile::default(),
+
+            edit_first_name: LineEdit::new_alloc(),
+            edit_last_name: LineEdit::new_alloc(),
+            opt_age_stage: OptionButton::new_alloc(),
+            slider_height: HSlider::new_alloc(),
+            slider_build: HSlider::new_alloc(),
+            pick_skin_color: ColorPickerButton::new_alloc(),
+            pick_hair_color: ColorPickerButton::new_alloc(),
+            check_traits: vec![],
+            lab_error: Label::new_alloc(),
+
+            base,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.setup_ui();
+        self.base_mut().hide();
+    }
+}
+
+#[godot_api]
+impl UiCharacterCreator {
+    /// Create was pressed with a valid profile. Read it with [UiCharacterCreator::profile].
+    #[signal]
+    fn sig_person_created();
+
+    #[func]
+    fn on_randomize_pressed(&mut self) {
+        self.show_profile(&PersonProfile::random(&mut rand::rng()));
+    }
+
+    #[func]
+    fn on_create_pressed(&mut self) {
+        let profile = self.read_profile();
+        if let Err(e) = profile.validate() {
+            self.lab_error.set_text(&e);
+            return;
+        }
+
+        self.profile = profile;
+        self.close();
+        self.base_mut().emit_signal("sig_person_created", &[]);
+    }
+
+    #[func]
+    fn on_cancel_pressed(&mut self) {
+        self.close();
+    }
+}
+
+impl UiCharacterCreator {
+    fn setup_ui(&mut self) {
+        let this_gd = self.to_gd();
+
+        self.edit_first_name.set_placeholder("First name");
+        self.edit_last_name.set_placeholder("Last name");
+
+        for age_stage in AgeStage::ALL {
+            self.opt_age_stage.add_item(age_stage.to_label());
+        }
+
+        for (slider, min, max) in [
+            (&mut self.slider_height, 0.9, 1.1),
+            (&mut self.slider_build, 0.85, 1.15),
+        ] {
+            slider.set_min(min);
+            slider.set_max(max);
+            slider.set_step(0.01);
+            slider.set_h_size_flags(SizeFlags::EXPAND_FILL);
+        }
+
+        self.pick_skin_color.set_edit_alpha(false);
+        self.pick_hair_color.set_edit_alpha(false);
+
+        let mut grid = GridContainer::new_alloc();
+        grid.set_columns(2);
+        let rows: [(&str, Gd<Control>); 7] = [
+            ("First name", self.edit_first_name.clone().upcast()),
+            ("Last name", self.edit_last_name.clone().upcast()),
+            ("Age", self.opt_age_stage.clone().upcast()),
+            ("Height", self.slider_height.clone().upcast()),
+            ("Build", self.slider_build.clone().upcast()),
+            ("Skin", self.pick_skin_color.clone().upcast()),
+            ("Hair", self.pick_hair_color.clone().upcast()),
+        ];
+        for (label, control) in rows {
+            let mut lab = Label::new_alloc();
+            lab.set_text(label);
+            grid.add_child(&lab);
+            grid.add_child(&control);
+        }
+
+        let mut lab_traits = Label::new_alloc();
+        lab_traits.set_text(&format!("Traits (up to {})", PersonProfile::MAX_TRAITS));
+        let mut grid_traits = GridContainer::new_alloc();
+        grid_traits.set_columns(3);
+        for person_trait in PersonTrait::ALL {
+            let mut check = CheckBox::new_alloc();
+            check.set_text(person_trait.to_label());
+            grid_traits.add_child(&check);
+            self.check_traits.push(check);
+        }
+
+        let mut buttons = HBoxContainer::new_alloc();
+        for (text, func) in [
+            ("Randomize", "on_randomize_pressed"),
+            ("Create", "on_create_pressed"),
+            ("Cancel", "on_cancel_pressed"),
+        ] {
+            let mut button = Button::new_alloc();
+            button.set_text(text);
+            button.connect("pressed", &this_gd.callable(func));
+            buttons.add_child(&button);
+        }
+
+        let mut vbox = VBoxContainer::new_alloc();
+        let mut lab_title = Label::new_alloc();
+        lab_title.set_text("New person");
+        vbox.add_child(&lab_title);
+        vbox.add_child(&grid);
+        vbox.add_child(&lab_traits);
+        vbox.add_child(&grid_traits);
+        vbox.add_child(&self.lab_error);
+        vbox.add_child(&buttons);
+
+        self.base_mut().add_child(&vbox);
+        self.base_mut()
+            .set_custom_minimum_size(Vector2::new(MIN_W, 0.0));
+        self.base_mut().set_anchors_preset(LayoutPreset::CENTER);
+    }
+
+    /// Show the window, starting from a random person.
+    pub fn open(&mut self) {
+        self.show_profile(&PersonProfile::random(&mut rand::rng()));
+        self.lab_error.set_text("");
+        self.base_mut().show();
+    }
+
+    pub fn close(&mut self) {
+        self.base_mut().hide();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.base().is_visible()
+    }
+
+    /// The last person created.
+    pub fn profile(&self) -> &PersonProfile {
+        &self.profile
+    }
+
+    fn show_profile(&mut self, profile: &PersonProfile) {
+        self.edit_first_name.set_text(&profile.first_name);
+        self.edit_last_name.set_text(&profile.last_name);
+        let age_index = AgeStage::ALL
+            .iter()
+            .position(|a| *a == profile.age_stage)
+            .unwrap_or(0);
+        self.opt_age_stage.select(age_index as i32);
+        self.slider_height.set_value(profile.height as f64);
+        self.slider_build.set_value(profile.build as f64);
+        self.pick_skin_color.set_pick_color(profile.skin_color());
+        self.pick_hair_color.set_pick_color(profile.hair_color());
+        for (check, person_trait) in self.check_traits.iter_mut().zip(PersonTrait::ALL) {
+            check.set_pressed(profile.traits.contains(&person_trait));
+        }
+    }
+
+    fn read_profile(&self) -> PersonProfile {
+        let rgb = |color: Color| [color.r, color.g, color.b];
+        let age_stage = AgeStage::ALL
+            .get(self.opt_age_stage.get_selected().max(0) as usize)
+            .copied()
+            .unwrap_or_default();
+
+        PersonProfile {
+            first_name: self.edit_first_name.get_text().to_string(),
+            last_name: self.edit_last_name.get_text().to_string(),
+            age_stage,
+            height: self.slider_height.get_value() as f32,
+            build: self.slider_build.get_value() as f32,
+            skin_color: rgb(self.pick_skin_color.get_pick_color()),
+            hair_color: rgb(self.pick_hair_color.get_pick_color()),
+            traits: self
+                .check_traits
+                .iter()
+                .zip(PersonTrait::ALL)
+                .filter(|(check, _)| check.is_pressed())
+                .map(|(_, person_trait)| person_trait)
+                .collect(),
+        }
+    }
+}
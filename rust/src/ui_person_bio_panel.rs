@@ -6,12 +6,14 @@
 use godot::prelude::*;
 
 use godot::classes::{
-    text_server::AutowrapMode, IMarginContainer, Label, MarginContainer, VBoxContainer,
+    text_server::AutowrapMode, IMarginContainer, Label, MarginContainer, OptionButton, ProgressBar,
+    VBoxContainer,
 };
 
-use crate::{person::TaskState, Person};
+use crate::{person::TaskState, Autonomy, Person};
 
 const MIN_W: f32 = 128.0;
+const BAR_H: f32 = 8.0;
 
 /// A real human bean
 #[derive(Debug, GodotClass)]
@@ -21,6 +23,8 @@ pub struct UiPersonBioPanel {
 
     lab_person_name: Gd<Label>,
     lab_person_task: Gd<Label>,
+    bar_task_progress: Gd<ProgressBar>,
+    opt_autonomy: Gd<OptionButton>,
 
     base: Base<MarginContainer>,
 }
@@ -33,6 +37,8 @@ impl IMarginContainer for UiPersonBioPanel {
 
             lab_person_name: Label::new_alloc(),
             lab_person_task: Label::new_alloc(),
+            bar_task_progress: ProgressBar::new_alloc(),
+            opt_autonomy: OptionButton::new_alloc(),
 
             base,
         }
@@ -47,6 +53,21 @@ impl IMarginContainer for UiPersonBioPanel {
     }
 }
 
+#[godot_api]
+impl UiPersonBioPanel {
+    /// Item 0 is "use world default", the rest are [Autonomy::ALL].
+    #[func]
+    fn on_autonomy_selected(&mut self, index: i64) {
+        let Some(person) = &mut self.selected_person else {
+            return;
+        };
+        let autonomy = (index as usize)
+            .checked_sub(1)
+            .and_then(|i| Autonomy::ALL.get(i).copied());
+        person.bind_mut().set_autonomy(autonomy);
+    }
+}
+
 impl UiPersonBioPanel {
     fn setup_ui(&mut self) {
         self.lab_person_name.set_name("lab_person_name");
@@ -57,9 +78,28 @@ impl UiPersonBioPanel {
         self.lab_person_task
             .set_autowrap_mode(AutowrapMode::ARBITRARY);
 
+        self.bar_task_progress.set_name("bar_task_progress");
+        self.bar_task_progress.set_max(1.0);
+        self.bar_task_progress.set_show_percentage(false);
+        self.bar_task_progress
+            .set_custom_minimum_size(Vector2::new(MIN_W, BAR_H));
+
+        self.opt_autonomy.set_name("opt_autonomy");
+        self.opt_autonomy.set_tooltip_text("Autonomy");
+        self.opt_autonomy.add_item("Autonomy: Default");
+        for autonomy in Autonomy::ALL {
+            self.opt_autonomy
+                .add_item(format!("Autonomy: {}", autonomy.to_label()).as_str());
+        }
+        let on_autonomy_selected = self.to_gd().callable("on_autonomy_selected");
+        self.opt_autonomy
+            .connect("item_selected", &on_autonomy_selected);
+
         let mut vbox = VBoxContainer::new_alloc();
         vbox.add_child(&self.lab_person_name);
         vbox.add_child(&self.lab_person_task);
+        vbox.add_child(&self.bar_task_progress);
+        vbox.add_child(&self.opt_autonomy);
 
         self.base_mut().add_child(&vbox);
         self.base_mut()
@@ -75,24 +115,43 @@ impl UiPersonBioPanel {
             self.show_placeholder();
             return;
         };
-        self.lab_person_name
-            .set_text(&target.base().get_name().to_string());
-
-        let task_desc = target.task().action().to_present_tense();
-        let status_desc = match target.task().state() {
-            TaskState::Init => "just started",
-            TaskState::Moving => "moving to location",
-            TaskState::Waiting => "waiting for partner",
-            TaskState::InProgress => "in progress",
-            TaskState::Done => "done",
+        self.lab_person_name.set_text(&target.profile().full_name());
+
+        let task = target.task();
+        let task_desc = task.action().to_present_tense();
+        let (progress, tooltip) = match task.state() {
+            TaskState::Init | TaskState::Moving | TaskState::Waiting => {
+                (0.0, "Getting ready".into())
+            }
+            TaskState::InProgress => (
+                task.progress(target.needs()),
+                format!("{:.0}s left at most", task.time_left()),
+            ),
+            TaskState::Done => (1.0, "Done".into()),
         };
 
-        self.lab_person_task
-            .set_text(format!("{task_desc} ({status_desc})").as_str());
+        self.lab_person_task.set_text(&task_desc);
+        self.bar_task_progress.set_value(progress);
+        self.bar_task_progress.set_tooltip_text(&tooltip);
+
+        let autonomy_index = match target.autonomy() {
+            Some(autonomy) => {
+                Autonomy::ALL
+                    .iter()
+                    .position(|a| *a == autonomy)
+                    .unwrap_or(0)
+                    + 1
+            }
+            None => 0,
+        } as i32;
+        if self.opt_autonomy.get_selected() != autonomy_index {
+            self.opt_autonomy.select(autonomy_index);
+        }
     }
 
     fn show_placeholder(&mut self) {
         self.lab_person_name.set_text("NONE!");
         self.lab_person_task.set_text("NONE!");
+        self.bar_task_progress.set_value(0.0);
     }
 }
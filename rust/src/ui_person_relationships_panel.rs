@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+//! Class: [UiPersonRelationshipsPanel]
+//! Desc: A panel that shows how a person feels about others.
+//!
+use godot::{
+    classes::{GridContainer, IMarginContainer, Label, MarginContainer, ProgressBar},
+    prelude::*,
+};
+use uuid::Uuid;
+
+use crate::Person;
+
+const BAR_W: f32 = 64.0;
+const BAR_H: f32 = 8.0;
+
+#[derive(Debug)]
+struct RelationshipRow {
+    uuid: Uuid,
+    lab_name: Gd<Label>,
+    lab_label: Gd<Label>,
+    bar_friendship: Gd<ProgressBar>,
+    bar_romance: Gd<ProgressBar>,
+}
+
+#[derive(Debug, GodotClass)]
+#[class(base=MarginContainer)]
+pub struct UiPersonRelationshipsPanel {
+    selected_person: Option<Gd<Person>>,
+
+    grid: Gd<GridContainer>,
+    lab_placeholder: Gd<Label>,
+    rows: Vec<RelationshipRow>,
+
+    base: Base<MarginContainer>,
+}
+
+#[godot_api]
+impl IMarginContainer for UiPersonRelationshipsPanel {
+    fn init(base: Base<Self::Base>) -> Self {
+        Self {
+            selected_person: None,
+
+            grid: GridContainer::new_alloc(),
+            lab_placeholder: Label::new_alloc(),
+            rows: vec![],
+
+            base,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.setup_ui();
+    }
+
+    fn process(&mut self, _delta: f64) {
+        self.show_stats();
+    }
+}
+
+impl UiPersonRelationshipsPanel {
+    fn setup_ui(&mut self) {
+        let mut grid = self.grid.clone();
+        grid.set_columns(4);
+        grid.set_name("grid");
+
+        let mut lab_placeholder = self.lab_placeholder.clone();
+        lab_placeholder.set_text("Doesn't know anyone yet");
+        lab_placeholder.set_name("lab_placeholder");
+
+        self.base_mut().add_child(&grid);
+        self.base_mut().add_child(&lab_placeholder);
+    }
+
+    pub fn select_person(&mut self, person: Option<Gd<Person>>) {
+        self.selected_person = person;
+    }
+
+    fn show_stats(&mut self) {
+        let Some(person) = self.selected_person.clone() else {
+            self.clear_rows();
+            return;
+        };
+        let person = person.bind();
+        let world = person.world().bind();
+
+        // Only people who are still around
+        let mut uuids: Vec<Uuid> = person
+            .relationships()
+            .keys()
+            .filter(|uuid| world.get_person(uuid).is_some())
+            .copied()
+            .collect();
+        uuids.sort();
+        if uuids != self.rows.iter().map(|r| r.uuid).collect::<Vec<_>>() {
+            self.clear_rows();
+            for uuid in &uuids {
+                self.add_row(*uuid);
+            }
+        }
+        self.lab_placeholder.set_visible(self.rows.is_empty());
+
+        for row in &mut self.rows {
+            let Some(relationship) = person.relationships().get(&row.uuid) else {
+                continue;
+            };
+            if let Some(other) = world.get_person(&row.uuid) {
+                row.lab_name.set_text(&other.bind().profile().full_name());
+            }
+            row.lab_label.set_text(relationship.label().to_label());
+            row.bar_friendship.set_value(relationship.friendship);
+            row.bar_romance.set_value(relationship.romance);
+        }
+    }
+
+    fn add_row(&mut self, uuid: Uuid) {
+        fn build_bar(min: f64, tooltip: &str) -> Gd<ProgressBar> {
+            let mut bar = ProgressBar::new_alloc();
+            bar.set_min(min);
+            bar.set_max(1.0);
+            bar.set_show_percentage(false);
+            bar.set_custom_minimum_size(Vector2::new(BAR_W, BAR_H));
+            bar.set_tooltip_text(tooltip);
+            bar
+        }
+
+        let row = RelationshipRow {
+            uuid,
+            lab_name: Label::new_alloc(),
+            lab_label: Label::new_alloc(),
+            bar_friendship: build_bar(-1.0, "Friendship"),
+            bar_romance: build_bar(0.0, "Romance"),
+        };
+
+        self.grid.add_child(&row.lab_name);
+        self.grid.add_child(&row.lab_label);
+        self.grid.add_child(&row.bar_friendship);
+        self.grid.add_child(&row.bar_romance);
+        self.rows.push(row);
+    }
+
+    fn clear_rows(&mut self) {
+        for mut row in self.rows.drain(..) {
+            row.lab_name.queue_free();
+            row.lab_label.queue_free();
+            row.bar_friendship.queue_free();
+            row.bar_romance.queue_free();
+        }
+    }
+}
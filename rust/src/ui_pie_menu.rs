@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+//! Class: [UiPieMenu]
+//! Desc: A radial context menu that pops up at the cursor.
+//!
+use std::f32::consts::{PI, TAU};
+
+use godot::classes::{
+    control::{LayoutPreset, MouseFilter},
+    Button, Control, IControl, InputEvent, InputEventMouseButton,
+};
+use godot::global::MouseButton;
+use godot::prelude::*;
+
+const RADIUS: f32 = 72.0;
+const BUTTON_SIZE: Vector2 = Vector2::new(112.0, 28.0);
+
+#[derive(Debug, Clone)]
+pub struct UiPieMenuEntry {
+    /// Sent back in [UiPieMenu::sig_entry_picked]
+    pub key: String,
+    pub label: String,
+    /// Why this entry can't be picked, if it can't.
+    pub disabled_reason: Option<String>,
+}
+
+#[derive(Debug, GodotClass)]
+#[class(base=Control)]
+pub struct UiPieMenu {
+    buttons: Vec<Gd<Button>>,
+
+    base: Base<Control>,
+}
+
+#[godot_api]
+impl IControl for UiPieMenu {
+    fn init(base: Base<Self::Base>) -> Self {
+        Self {
+            buttons: vec![],
+
+            base,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.base_mut().set_anchors_preset(LayoutPreset::FULL_RECT);
+        self.base_mut().set_mouse_filter(MouseFilter::IGNORE);
+        self.base_mut().hide();
+    }
+
+    fn unhandled_input(&mut self, event: Gd<InputEvent>) {
+        if !self.base().is_visible() {
+            return;
+        }
+
+        let cancel = if event.is_action_pressed("ui_cancel") {
+            true
+        } else if let Ok(event) = event.try_cast::<InputEventMouseButton>() {
+            event.get_button_index() == MouseButton::RIGHT && event.is_pressed()
+        } else {
+            false
+        };
+
+        if cancel {
+            self.close();
+            if let Some(mut viewport) = self.base().get_viewport() {
+                viewport.set_input_as_handled();
+            }
+        }
+    }
+}
+
+#[godot_api]
+impl UiPieMenu {
+    /// An entry was picked. The menu closes itself.
+    #[signal]
+    fn sig_entry_picked(key: GString);
+
+    #[func]
+    fn on_entry_pressed(&mut self, key: GString) {
+        self.close();
+        self.base_mut()
+            .emit_signal("sig_entry_picked", &[key.to_variant()]);
+    }
+}
+
+impl UiPieMenu {
+    /// Show entries in a circle around `center`.
+    pub fn open(&mut self, center: Vector2, entries: Vec<UiPieMenuEntry>) {
+        self.clear();
+
+        let this_gd = self.to_gd();
+        let count = entries.len().max(1) as f32;
+
+        for (i, entry) in entries.into_iter().enumerate() {
+            let angle = -PI / 2.0 + i as f32 * TAU / count;
+            let offset = Vector2::new(angle.cos(), angle.sin()) * RADIUS;
+
+            let mut button = Button::new_alloc();
+            button.set_text(&entry.label);
+            button.set_custom_minimum_size(BUTTON_SIZE);
+            button.set_position(center + offset - BUTTON_SIZE / 2.0);
+            button.set_name(format!("button_{}", entry.key).as_str());
+            if let Some(reason) = &entry.disabled_reason {
+                button.set_disabled(true);
+                button.set_tooltip_text(reason);
+            }
+            button.connect(
+                "pressed",
+                &this_gd
+                    .callable("on_entry_pressed")
+                    .bindv(&varray![GString::from(entry.key)]),
+            );
+
+            self.base_mut().add_child(&button);
+            self.buttons.push(button);
+        }
+
+        self.base_mut().show();
+    }
+
+    pub fn close(&mut self) {
+        self.clear();
+        self.base_mut().hide();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.base().is_visible()
+    }
+
+    fn clear(&mut self) {
+        for mut button in self.buttons.drain(..) {
+            button.queue_free();
+        }
+    }
+}
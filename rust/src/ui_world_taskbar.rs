@@ -3,11 +3,16 @@
 //! Desc: The large bottom bar in home view
 //!
 use godot::classes::{
-    control::LayoutPreset, Control, HBoxContainer, IPanelContainer, PanelContainer, StyleBoxTexture,
+    control::LayoutPreset, Button, Control, HBoxContainer, IPanelContainer, Label, OptionButton,
+    PanelContainer, StyleBoxTexture,
 };
 use godot::prelude::*;
+use uuid::Uuid;
 
-use crate::{Person, UiPersonBioPanel, UiPersonNeedsPanel};
+use crate::{
+    Autonomy, GameClock, Person, UiActionQueue, UiPersonBioPanel, UiPersonNeedsPanel,
+    UiPersonRelationshipsPanel, Weather,
+};
 
 const BAR_H: f32 = 32.0;
 
@@ -20,10 +25,19 @@ pub struct UiWorldTaskbar {
     hbox: Gd<HBoxContainer>,
 
     ui_world_mode_select: Gd<Control>,
+    ui_autonomy_select: Gd<OptionButton>,
+    lab_household: Gd<Label>,
+    lab_clock: Gd<Label>,
+    lab_notice: Gd<Label>,
+    /// One toggle button per household member, in household order
+    hbox_portraits: Gd<HBoxContainer>,
+    portraits: Vec<(Uuid, Gd<Button>)>,
 
     _ui_playmode_root: Gd<HBoxContainer>,
     ui_playmode_person_bio: Gd<UiPersonBioPanel>,
     ui_playmode_person_needs: Gd<UiPersonNeedsPanel>,
+    ui_playmode_action_queue: Gd<UiActionQueue>,
+    ui_playmode_person_relationships: Gd<UiPersonRelationshipsPanel>,
 
     _ui_buildmode_root: Gd<HBoxContainer>,
 
@@ -42,10 +56,18 @@ impl IPanelContainer for UiWorldTaskbar {
             hbox: HBoxContainer::new_alloc(),
 
             ui_world_mode_select: Control::new_alloc(),
+            ui_autonomy_select: OptionButton::new_alloc(),
+            lab_household: Label::new_alloc(),
+            lab_clock: Label::new_alloc(),
+            lab_notice: Label::new_alloc(),
+            hbox_portraits: HBoxContainer::new_alloc(),
+            portraits: vec![],
 
             _ui_playmode_root: HBoxContainer::new_alloc(),
             ui_playmode_person_bio: UiPersonBioPanel::new_alloc(),
             ui_playmode_person_needs: UiPersonNeedsPanel::new_alloc(),
+            ui_playmode_action_queue: UiActionQueue::new_alloc(),
+            ui_playmode_person_relationships: UiPersonRelationshipsPanel::new_alloc(),
 
             _ui_buildmode_root: HBoxContainer::new_alloc(),
 
@@ -62,6 +84,37 @@ impl IPanelContainer for UiWorldTaskbar {
     }
 }
 
+#[godot_api]
+impl UiWorldTaskbar {
+    /// Player changed the default autonomy.
+    #[signal]
+    fn sig_autonomy_changed(autonomy: Autonomy);
+
+    /// A household member's portrait was clicked.
+    #[signal]
+    fn sig_portrait_pressed(uuid: GString);
+
+    #[func]
+    fn on_portrait_pressed(&mut self, uuid: GString) {
+        // Selection decides what's pressed, not the click
+        self.update_portraits();
+        // Deferred, listeners rebuild portraits while this one is still bound
+        self.base_mut().call_deferred(
+            "emit_signal",
+            &["sig_portrait_pressed".to_variant(), uuid.to_variant()],
+        );
+    }
+
+    #[func]
+    fn on_autonomy_selected(&mut self, index: i64) {
+        let Some(autonomy) = Autonomy::ALL.get(index as usize) else {
+            return;
+        };
+        self.base_mut()
+            .emit_signal("sig_autonomy_changed", &[autonomy.to_variant()]);
+    }
+}
+
 impl UiWorldTaskbar {
     fn setup_ui(&mut self) {
         self.base_mut()
@@ -73,10 +126,33 @@ impl UiWorldTaskbar {
         self.base_mut()
             .set_anchors_preset(LayoutPreset::BOTTOM_LEFT);
 
+        let on_autonomy_selected = self.to_gd().callable("on_autonomy_selected");
+        let mut ui_autonomy_select = self.ui_autonomy_select.clone();
+        for autonomy in Autonomy::ALL {
+            ui_autonomy_select.add_item(format!("Autonomy: {}", autonomy.to_label()).as_str());
+        }
+        ui_autonomy_select.set_tooltip_text("Default autonomy for everyone");
+        ui_autonomy_select.connect("item_selected", &on_autonomy_selected);
+        ui_autonomy_select.set_name("ui_autonomy_select");
+        self.set_autonomy(Autonomy::default());
+
+        self.lab_household.set_name("lab_household");
+        self.lab_clock.set_name("lab_clock");
+        self.lab_notice.set_name("lab_notice");
+        self.hbox_portraits.set_name("hbox_portraits");
+        self.set_household(None);
+
         let mut hbox = self.hbox.clone();
         hbox.add_child(&self.ui_world_mode_select);
+        hbox.add_child(&self.lab_clock);
+        hbox.add_child(&self.lab_notice);
+        hbox.add_child(&self.lab_household);
+        hbox.add_child(&self.hbox_portraits);
+        hbox.add_child(&self.ui_autonomy_select);
         hbox.add_child(&self.ui_playmode_person_bio);
         hbox.add_child(&self.ui_playmode_person_needs);
+        hbox.add_child(&self.ui_playmode_person_relationships);
+        hbox.add_child(&self.ui_playmode_action_queue);
 
         self.base_mut().add_child(&hbox);
 
@@ -89,6 +165,71 @@ impl UiWorldTaskbar {
     //    //self.ui_world_mode_select.bind_mut().connect_world(world)
     //}
 
+    /// Show default autonomy. Doesn't emit [Self::sig_autonomy_changed].
+    pub fn set_autonomy(&mut self, autonomy: Autonomy) {
+        if let Some(index) = Autonomy::ALL.iter().position(|a| *a == autonomy) {
+            self.ui_autonomy_select.select(index as i32);
+        }
+    }
+
+    /// Show time, date and weather.
+    pub fn set_clock(&mut self, clock: &GameClock, weather: Weather) {
+        let text = format!(
+            "{}\n{} · {}",
+            clock.to_time_label(),
+            clock.to_date_label(),
+            weather.to_label()
+        );
+        if self.lab_clock.get_text().to_string() != text {
+            self.lab_clock.set_text(&text);
+        }
+    }
+
+    /// Short message next to the clock, like why time stopped skipping.
+    pub fn set_notice(&mut self, text: &str) {
+        self.lab_notice.set_text(text);
+    }
+
+    /// Show name and funds of the active household.
+    pub fn set_household(&mut self, household: Option<(String, i64)>) {
+        let text = match household {
+            Some((name, funds)) => format!("{name} household\n§{funds}"),
+            None => "No household".into(),
+        };
+        self.lab_household.set_text(&text);
+    }
+
+    /// Rebuild portraits for household members: uuid, name and skin colour.
+    pub fn set_household_members(&mut self, members: Vec<(Uuid, String, Color)>) {
+        for (_, mut button) in self.portraits.drain(..) {
+            button.queue_free();
+        }
+
+        let on_portrait_pressed = self.to_gd().callable("on_portrait_pressed");
+        for (uuid, name, color) in members {
+            let mut button = Button::new_alloc();
+            button.set_text(&name);
+            button.set_toggle_mode(true);
+            button.set_self_modulate(color);
+            button.set_tooltip_text("Click to select, Shift+click to move left");
+            button.connect(
+                "pressed",
+                &on_portrait_pressed.bind(&[uuid.to_string().to_variant()]),
+            );
+            self.hbox_portraits.add_child(&button);
+            self.portraits.push((uuid, button));
+        }
+        self.update_portraits();
+    }
+
+    /// Press the selected person's portrait only.
+    fn update_portraits(&mut self) {
+        let selected = self.selected_person.as_ref().map(|p| p.bind().uuid());
+        for (uuid, button) in &mut self.portraits {
+            button.set_pressed_no_signal(Some(*uuid) == selected);
+        }
+    }
+
     pub fn select_person(&mut self, person: Option<Gd<Person>>) {
         self.ui_playmode_person_bio
             .bind_mut()
@@ -98,14 +239,27 @@ impl UiWorldTaskbar {
             .bind_mut()
             .select_person(person.clone());
 
+        self.ui_playmode_action_queue
+            .bind_mut()
+            .select_person(person.clone());
+
+        self.ui_playmode_person_relationships
+            .bind_mut()
+            .select_person(person.clone());
+
         if person.is_some() {
             self.ui_playmode_person_bio.show();
             self.ui_playmode_person_needs.show();
+            self.ui_playmode_action_queue.show();
+            self.ui_playmode_person_relationships.show();
         } else {
             self.ui_playmode_person_bio.hide();
             self.ui_playmode_person_needs.hide();
+            self.ui_playmode_action_queue.hide();
+            self.ui_playmode_person_relationships.hide();
         }
 
         self.selected_person = person;
+        self.update_portraits();
     }
 }
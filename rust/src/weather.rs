@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: LGPL-3.0-or-later
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{NeedDecay, Season};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Cloudy,
+    Rain,
+    Snow,
+}
+
+impl Weather {
+    pub const ALL: [Weather; 4] = [
+        Weather::Clear,
+        Weather::Cloudy,
+        Weather::Rain,
+        Weather::Snow,
+    ];
+
+    /// Weather for a new day. Snow only falls in winter.
+    pub fn random(season: Season, rng: &mut impl Rng) -> Self {
+        // Chances of each of [Self::ALL]
+        let weights: [u32; 4] = match season {
+            Season::Spring => [5, 3, 3, 0],
+            Season::Summer => [7, 2, 1, 0],
+            Season::Autumn => [3, 4, 4, 0],
+            Season::Winter => [2, 3, 1, 4],
+        };
+        let mut roll = rng.random_range(0..weights.iter().sum::<u32>());
+        for (weather, weight) in Self::ALL.into_iter().zip(weights) {
+            if roll < weight {
+                return weather;
+            }
+            roll -= weight;
+        }
+        Weather::Clear
+    }
+
+    pub fn to_label(self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Cloudy => "Cloudy",
+            Weather::Rain => "Rain",
+            Weather::Snow => "Snow",
+        }
+    }
+
+    /// 0.0..=1.0, how grey the sky gets.
+    pub fn cloud_cover(self) -> f32 {
+        match self {
+            Weather::Clear => 0.0,
+            Weather::Cloudy => 0.6,
+            Weather::Rain => 0.85,
+            Weather::Snow => 0.7,
+        }
+    }
+
+    /// Multiplier for sunlight that makes it through.
+    pub fn sun_factor(self) -> f32 {
+        1.0 - self.cloud_cover() * 0.7
+    }
+
+    /// 0.0..=1.0, how pleasant it is to be outside, daylight aside.
+    pub fn outdoor_comfort(self) -> f64 {
+        match self {
+            Weather::Clear => 1.0,
+            Weather::Cloudy => 0.7,
+            Weather::Rain => 0.1,
+            Weather::Snow => 0.3,
+        }
+    }
+
+    /// What being out in it does to people.
+    pub fn need_decay(self) -> NeedDecay {
+        match self {
+            Weather::Clear | Weather::Cloudy => NeedDecay::default(),
+            Weather::Rain => NeedDecay {
+                comfort: 2.0,
+                hygiene: 1.3,
+                ..Default::default()
+            },
+            Weather::Snow => NeedDecay {
+                comfort: 2.5,
+                ..Default::default()
+            },
+        }
+    }
+}
@@ -8,33 +8,83 @@ use godot::prelude::*;
 
 use godot::classes::{
     control::{LayoutPreset, MouseFilter, SizeFlags},
+    file_access::ModeFlags,
     node::ProcessMode,
-    BoxShape3D, Control, Engine, HBoxContainer, InputEvent, InputEventMouseButton, MeshInstance3D,
-    Shape3D, VBoxContainer,
+    BoxShape3D, Control, FileAccess, HBoxContainer, InputEvent, InputEventMouseButton, Material,
+    MeshInstance3D, PhysicsRayQueryParameters3D, Shape3D, VBoxContainer,
 };
-use godot::global::MouseButton;
+use godot::global::{Key, MouseButton};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use uuid::Uuid;
 
 use crate::{
-    lot_builder::LotBuilder, lot_data, ActionAdvertisement, ActionAdvertisementStat,
-    CameraRigOrbit, Furniture, Person, SpiritLevel, TimeScale, UiDebugOvl, UiWorldTaskbar,
-    WorldEnv, WorldViewMode,
+    lot_builder::LotBuilder,
+    lot_data,
+    save_data::{PersonSave, WorldSave},
+    Action, ActionAdvertisement, ActionAdvertisementSource, ActionAdvertisementStat, Autonomy,
+    CameraBookmarks, CameraRigOrbit, ClockTick, EnvironmentScore, EventScheduler, FiredEvent,
+    Furniture, GameClock, Household, LampKind, Person, PersonProfile, PersonTrait, Season,
+    SpiritLevel, TimeScale, UiCharacterCreator, UiDebugOvl, UiPieMenu, UiPieMenuEntry,
+    UiWorldTaskbar, WakeReason, Weather, Weekday, WorldEnv, WorldViewMode,
 };
 
+const SAVE_PATH: &str = "user://savegame.json";
+/// Width and depth of the lot, starting from origin.
+const LOT_SIZE: f32 = 32.0;
+const LOT_NAME: &str = "Sunny Acres";
+/// Same seed, same story.
+const DEFAULT_SEED: u64 = 1;
+/// Seconds of simulation per tick, whatever the speed.
+const SIM_STEP: f64 = 1.0 / 30.0;
+/// Share of daylight that makes it indoors, through windows.
+const INDOOR_DAYLIGHT: f64 = 0.4;
+/// Below this brightness, people want a light on.
+const DARK_THRESHOLD: f64 = 0.4;
+/// Ultra speed stops at this hour, if the whole household is asleep.
+const MORNING_HOUR: u64 = 7;
+/// Ultra speed stops when a playable person's need drops below this.
+const CRITICAL_NEED: f64 = 0.1;
+/// Taken from every household with a lot on Monday
+const WEEKLY_BILLS: i64 = 250;
+/// Game minutes a visitor stays before heading home.
+const VISIT_MINUTES: f64 = 180.0;
+
 #[derive(Debug, GodotClass)]
 #[class(base=Node)]
 pub struct World {
     people: HashMap<Uuid, Gd<Person>>,
+    /// Order people were added in. Iterate this, not [Self::people], for stable results.
+    people_order: Vec<Uuid>,
+    households: Vec<Household>,
+    /// The one the player controls. Everyone else is AI only.
+    active_household: Option<Uuid>,
     furniture: Vec<Gd<Furniture>>,
     selected_person: Option<Gd<Person>>,
     view_mode: WorldViewMode,
     time_scale: TimeScale,
-    time_of_day: Duration,
+    /// Simulation time owed, less than one [SIM_STEP] after ticking
+    sim_accumulator: f64,
+    clock: GameClock,
+    /// Game minutes per real second at regular speed, kept in sync with [Self::clock]
+    #[export(range = (0.0, 60.0, or_greater))]
+    #[var(get, set = set_minutes_per_second)]
+    minutes_per_second: f64,
+    scheduler: EventScheduler,
+    /// Default for people who don't have their own setting.
+    autonomy: Autonomy,
+    /// Seeds people's AI as they're added, and rolls dice for the world itself.
+    rng: StdRng,
+    /// Saved camera views, by lot name
+    camera_bookmarks: HashMap<String, CameraBookmarks>,
 
     lot_builder: Option<Gd<LotBuilder>>,
 
     ui_root: Gd<VBoxContainer>,
     ui_taskbar: Gd<UiWorldTaskbar>,
+    ui_pie_menu: Gd<UiPieMenu>,
+    ui_character_creator: Gd<UiCharacterCreator>,
+    /// What the open pie menu is about
+    pie_menu_target: Option<Gd<Furniture>>,
     spirit_level: Gd<SpiritLevel>,
 
     scn_root: Gd<Node3D>,
@@ -43,6 +93,12 @@ pub struct World {
     scn_lot_walls: Gd<MeshInstance3D>,
 
     data_walls: lot_data::Walls,
+    /// Derived from [Self::data_walls]
+    data_rooms: lot_data::RoomMap,
+    /// [Self::environment_score_of] each room, by room index. See [Self::update_environment]
+    room_environment: Vec<f64>,
+    /// [Self::environment_score_of] outdoors
+    outdoor_environment: f64,
 
     base: Base<Node>,
 }
@@ -59,19 +115,32 @@ impl INode for World {
         let scn_lot_walls = MeshInstance3D::new_alloc();
 
         let data_walls = lot_data::Walls::with_test_layout();
+        let data_rooms = lot_data::RoomMap::from_walls(&data_walls);
 
         Self {
             people: HashMap::new(),
+            people_order: vec![],
+            households: vec![],
+            active_household: None,
             furniture: vec![],
             selected_person: None,
             view_mode: WorldViewMode::default(),
             time_scale: TimeScale::Regular,
-            time_of_day: Duration::from_secs(360 * 10),
+            sim_accumulator: 0.0,
+            clock: GameClock::new(0, Duration::from_secs(360 * 10)),
+            minutes_per_second: GameClock::DEFAULT_MINUTES_PER_SECOND,
+            scheduler: EventScheduler::new(),
+            autonomy: Autonomy::default(),
+            rng: StdRng::seed_from_u64(DEFAULT_SEED),
+            camera_bookmarks: HashMap::new(),
 
             lot_builder: None,
 
             ui_root,
             ui_taskbar,
+            ui_pie_menu: UiPieMenu::new_alloc(),
+            ui_character_creator: UiCharacterCreator::new_alloc(),
+            pie_menu_target: None,
             spirit_level: SpiritLevel::new_alloc(),
 
             scn_root,
@@ -80,6 +149,9 @@ impl INode for World {
             scn_lot_walls,
 
             data_walls,
+            data_rooms,
+            room_environment: vec![],
+            outdoor_environment: 1.0,
 
             base,
         }
@@ -92,23 +164,54 @@ impl INode for World {
         self.setup_scene();
         self.setup_objects();
         self.setup_people();
+        self.setup_schedule();
+        self.update_terrain_season();
 
         self.base_mut().print_tree_pretty();
     }
 
     fn process(&mut self, delta: f64) {
         if !self.base().get_tree().unwrap().is_paused() {
-            // 1 sec realtime => 1 min game time
-            self.time_of_day += Duration::from_secs_f64(delta * 60.0);
+            self.sim_accumulator += delta * self.time_scale.speed();
+        }
+        let mut steps = 0;
+        // Past the limit, the simulation falls behind instead of freezing the game
+        let max_steps = self.time_scale.max_steps_per_frame();
+        while self.sim_accumulator >= SIM_STEP && steps < max_steps {
+            self.sim_accumulator -= SIM_STEP;
+            steps += 1;
+        }
+        self.sim_accumulator = self.sim_accumulator.min(SIM_STEP);
+
+        let world = self.to_gd();
+        let time_scale = self.time_scale;
+        {
+            // People look the world up while they tick
+            let _guard = self.base_mut();
+            for _ in 0..steps {
+                World::sim_tick(world.clone());
+                if world.bind().time_scale != time_scale {
+                    // Woke up from ultra speed, the rest of the frame's steps are too many
+                    break;
+                }
+            }
         }
-        const DAY_DURATION: Duration = Duration::from_secs(60 * 60 * 24);
-        if self.time_of_day >= DAY_DURATION {
-            self.time_of_day -= DAY_DURATION;
+        let alpha = (self.sim_accumulator / SIM_STEP) as f32;
+        for (_, mut person) in self.people_ordered() {
+            person.bind_mut().interpolate(alpha);
         }
-        self.scn_env.bind_mut().set_time(self.time_of_day);
+        self.scn_env.bind_mut().set_time(self.clock.time_of_day());
+        let weather = self.weather();
+        self.ui_taskbar.bind_mut().set_clock(&self.clock, weather);
 
         let input = Input::singleton();
 
+        if input.is_action_just_pressed("game_quicksave") {
+            self.save_game();
+        } else if input.is_action_just_pressed("game_quickload") {
+            self.load_game();
+        }
+
         if input.is_action_just_pressed("mode_play") {
             self.set_view_mode(WorldViewMode::Play);
             self.scn_lot_walls.show();
@@ -121,6 +224,8 @@ impl INode for World {
         }
 
         match self.view_mode {
+            // Typing a name shouldn't trigger hotkeys
+            WorldViewMode::Play if self.ui_character_creator.bind().is_open() => (),
             WorldViewMode::Play => {
                 if input.is_action_just_pressed("play_toggle_pause") {
                     let paused = self.base().get_tree().unwrap().is_paused();
@@ -131,19 +236,44 @@ impl INode for World {
                     self.set_time_scale(TimeScale::Fast);
                 } else if input.is_action_just_pressed("play_set_speed_3") {
                     self.set_time_scale(TimeScale::Superfast);
+                } else if input.is_action_just_pressed("play_set_speed_4") {
+                    self.set_time_scale(TimeScale::Ultra);
                 } else if input.is_action_just_pressed("play_cycle_characters") {
                     self.select_next_person();
+                } else if input.is_action_just_pressed("play_create_person") {
+                    if self.active_household().is_some() {
+                        self.ui_character_creator.bind_mut().open();
+                    } else {
+                        self.ui_taskbar
+                            .bind_mut()
+                            .set_notice("Pick a household to move someone in with");
+                    }
+                } else if input.is_action_just_pressed("play_spawn_visitor") {
+                    self.spawn_visitor();
+                } else if input.is_action_just_pressed("play_remove_person") {
+                    if let Some(uuid) = self.selected_person.as_ref().map(|p| p.bind().uuid()) {
+                        self.remove_person(uuid);
+                    }
                 }
             }
-            WorldViewMode::Buy => (),
-            WorldViewMode::Build => (),
+            WorldViewMode::Buy | WorldViewMode::Build => self.process_camera_bookmarks(),
         }
     }
 
     fn unhandled_input(&mut self, event: Gd<InputEvent>) {
         if let Ok(event) = event.clone().try_cast::<InputEventMouseButton>() {
+            if event.get_button_index() == MouseButton::LEFT && event.is_double_click() {
+                if let Some(position) = self.hovered_position() {
+                    self.scn_camera_rig.bind_mut().focus_on(position);
+                }
+            }
             if let MouseButton::LEFT = event.get_button_index() {
-                if event.is_pressed() {
+                // Clicks on entities are handled by their colliders.
+                if event.is_pressed() && self.view_mode == WorldViewMode::Play {
+                    self.wake(WakeReason::Player);
+                }
+                if event.is_pressed() && !self.is_entity_hovered() {
+                    self.ui_pie_menu.bind_mut().close();
                     self.select_person(None);
                 }
             }
@@ -153,6 +283,25 @@ impl INode for World {
 
 #[godot_api]
 impl World {
+    /// A new game hour started. `hour` is 0..24. Emitted deferred, once the tick is over.
+    #[signal]
+    fn sig_hour_started(day: i64, hour: i64);
+
+    /// A new game day started, counting from 0. Emitted deferred, once the tick is over.
+    #[signal]
+    fn sig_day_started(day: i64);
+
+    /// Ultra speed stopped, see [WakeReason::to_label]. Emitted deferred, once the tick is over.
+    #[signal]
+    fn sig_woke_up(reason: GString);
+
+    /// How many game minutes pass per second at regular speed.
+    #[func]
+    pub fn set_minutes_per_second(&mut self, minutes_per_second: f64) {
+        self.clock.set_minutes_per_second(minutes_per_second);
+        self.minutes_per_second = self.clock.minutes_per_second();
+    }
+
     #[func]
     fn get_view_mode(&self) -> WorldViewMode {
         self.view_mode
@@ -164,6 +313,11 @@ impl World {
             return;
         }
 
+        // Building and buying happen in plain sight, and paused
+        if mode != WorldViewMode::Play {
+            self.wake(WakeReason::Player);
+        }
+
         if mode != WorldViewMode::Build {
             if let Some(lot_builder) = &mut self.lot_builder {
                 self.data_walls = lot_builder.bind().wall_data().clone();
@@ -199,17 +353,138 @@ impl World {
         self.view_mode = mode;
     }
 
+    #[func]
+    fn on_autonomy_changed(&mut self, autonomy: Autonomy) {
+        self.autonomy = autonomy;
+    }
+
     #[func]
     fn on_person_selected(&mut self, person: Gd<Person>) {
+        if !self.is_playable(&person.bind().uuid()) {
+            return;
+        }
+        self.ui_pie_menu.bind_mut().close();
+        self.select_person(Some(person));
+    }
+
+    /// Character creator is done, bring the new person to life. They move in with the active
+    /// household, without one there's nowhere for them to live.
+    #[func]
+    fn on_person_created(&mut self) {
+        if self.active_household().is_none() {
+            return;
+        }
+        let profile = self.ui_character_creator.bind().profile().clone();
+        let mut person = Person::new(self.to_gd(), profile);
+        person.set_position(Vector3::new(LOT_SIZE / 2.0, 0.0, LOT_SIZE / 2.0));
+        let uuid = person.bind().uuid();
+
+        self.add_person(person.clone());
+        if let Some(household) = self.active_household_mut() {
+            household.add_member(uuid);
+        }
+        self.refresh_household_ui();
         self.select_person(Some(person));
     }
+
+    /// Click selects, shift+click moves the member one place earlier.
+    #[func]
+    fn on_portrait_pressed(&mut self, uuid: GString) {
+        let Ok(uuid) = Uuid::parse_str(&uuid.to_string()) else {
+            return;
+        };
+
+        if Input::singleton().is_key_pressed(Key::SHIFT) {
+            if let Some(household) = self.active_household_mut() {
+                if let Some(index) = household.members().iter().position(|m| *m == uuid) {
+                    household.move_member(&uuid, index.saturating_sub(1));
+                }
+            }
+            self.refresh_household_ui();
+            return;
+        }
+
+        let person = self.get_person(&uuid).cloned();
+        if let Some(person) = person {
+            self.on_person_selected(person);
+        }
+    }
+
+    /// Open the action menu for selected person.
+    #[func]
+    fn on_furniture_selected(&mut self, furniture: Gd<Furniture>) {
+        if self.view_mode != WorldViewMode::Play || self.selected_person.is_none() {
+            return;
+        }
+
+        self.scn_camera_rig
+            .bind_mut()
+            .focus_on(furniture.get_global_position());
+
+        let entries: Vec<UiPieMenuEntry> = furniture
+            .bind()
+            .available_actions()
+            .iter()
+            .map(|advert| {
+                let disabled_reason = if advert.required_people > 1 {
+                    Some("Needs a partner".into())
+                } else if advert.furniture().is_some_and(|f| f.bind().is_reserved()) {
+                    Some("In use".into())
+                } else {
+                    None
+                };
+                UiPieMenuEntry {
+                    key: advert.action_key.clone(),
+                    label: Action::in_place(&advert.action_key).to_menu_label(),
+                    disabled_reason,
+                }
+            })
+            .collect();
+        if entries.is_empty() {
+            return;
+        }
+
+        let Some(viewport) = self.base().get_viewport() else {
+            return;
+        };
+        let center = viewport.get_mouse_position();
+        self.ui_pie_menu.bind_mut().open(center, entries);
+        self.pie_menu_target = Some(furniture);
+    }
+
+    /// Queue picked action for selected person.
+    #[func]
+    fn on_pie_menu_entry_picked(&mut self, key: GString) {
+        let Some(furniture) = self.pie_menu_target.take() else {
+            return;
+        };
+        let Some(person) = &mut self.selected_person else {
+            return;
+        };
+
+        let mut action = Action::in_place(&key.to_string());
+        action.object = Some(furniture);
+        person.bind_mut().queue_player_action(action);
+    }
 }
 
 impl World {
     pub fn advertisements(&self) -> Vec<ActionAdvertisement> {
         let mut vec: Vec<ActionAdvertisement> = vec![];
         for furniture in &self.furniture {
-            vec.extend(furniture.bind().available_actions().to_owned());
+            let furniture_ref = furniture.bind();
+            if furniture_ref.is_lamp() {
+                // Switch on when it's dark, off when there's daylight enough
+                let dark =
+                    self.ambient_brightness_at(furniture.get_global_position()) < DARK_THRESHOLD;
+                if dark == furniture_ref.is_lit() {
+                    continue;
+                }
+            }
+            vec.extend(furniture_ref.available_actions().to_owned());
+        }
+        for uuid in &self.people_order {
+            vec.extend(Person::social_advertisements(*uuid));
         }
         vec
     }
@@ -222,10 +497,410 @@ impl World {
         &self.people
     }
 
+    /// Everyone, in the order they were added.
+    pub fn people_ordered(&self) -> Vec<(Uuid, Gd<Person>)> {
+        self.people_order
+            .iter()
+            .filter_map(|uuid| Some((*uuid, self.people.get(uuid)?.clone())))
+            .collect()
+    }
+
+    /// Reseed world randomness. Affects people added afterwards.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn households(&self) -> &[Household] {
+        &self.households
+    }
+
+    pub fn add_household(&mut self, household: Household) {
+        self.households.push(household);
+    }
+
+    pub fn active_household(&self) -> Option<&Household> {
+        let uuid = self.active_household?;
+        self.households.iter().find(|h| h.uuid() == uuid)
+    }
+
+    fn active_household_mut(&mut self) -> Option<&mut Household> {
+        let uuid = self.active_household?;
+        self.households.iter_mut().find(|h| h.uuid() == uuid)
+    }
+
+    /// Hand control over to another household. Selection is dropped if it isn't theirs.
+    pub fn set_active_household(&mut self, uuid: Option<Uuid>) {
+        self.active_household = uuid;
+
+        let keep_selection = self
+            .selected_person
+            .as_ref()
+            .is_some_and(|p| self.is_playable(&p.bind().uuid()));
+        if !keep_selection {
+            self.select_person(None);
+        }
+
+        self.refresh_household_ui();
+    }
+
+    /// Show the active household's name, funds and members in the taskbar.
+    fn refresh_household_ui(&mut self) {
+        let info = self.active_household().map(|h| (h.name.clone(), h.funds));
+        let members: Vec<(Uuid, String, Color)> = self
+            .active_household()
+            .map(|h| h.members().to_vec())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|uuid| {
+                let person = self.get_person(uuid)?.bind();
+                let profile = person.profile();
+                Some((*uuid, profile.first_name.clone(), profile.skin_color()))
+            })
+            .collect();
+
+        let mut taskbar = self.ui_taskbar.bind_mut();
+        taskbar.set_household(info);
+        taskbar.set_household_members(members);
+    }
+
+    /// The household this person lives in, if any.
+    pub fn household_of(&self, uuid: &Uuid) -> Option<&Household> {
+        self.households.iter().find(|h| h.has_member(uuid))
+    }
+
+    /// Is this person in the active household.
+    pub fn is_playable(&self, uuid: &Uuid) -> bool {
+        self.active_household().is_some_and(|h| h.has_member(uuid))
+    }
+
     pub fn view_mode(&self) -> WorldViewMode {
         self.view_mode
     }
 
+    pub fn autonomy(&self) -> Autonomy {
+        self.autonomy
+    }
+
+    pub fn set_autonomy(&mut self, autonomy: Autonomy) {
+        self.autonomy = autonomy;
+        self.ui_taskbar.bind_mut().set_autonomy(autonomy);
+    }
+
+    pub fn clock(&self) -> &GameClock {
+        &self.clock
+    }
+
+    pub fn scheduler(&self) -> &EventScheduler {
+        &self.scheduler
+    }
+
+    pub fn scheduler_mut(&mut self) -> &mut EventScheduler {
+        &mut self.scheduler
+    }
+
+    fn on_scheduled_event(&mut self, event: &FiredEvent) {
+        // Only what happens to the player's household is worth stopping for
+        let involves_active = match event.key.as_str() {
+            "bills" => self.active_household().is_some_and(|h| h.lot.is_some()),
+            _ => event.subject.is_some_and(|uuid| self.is_playable(&uuid)),
+        };
+        if involves_active {
+            self.wake(WakeReason::ScheduledEvent(event.key.clone()));
+        }
+        match event.key.as_str() {
+            "bills" => {
+                for household in &mut self.households {
+                    household.pay_bills(WEEKLY_BILLS);
+                }
+                self.refresh_household_ui();
+            }
+            "visitor" => {
+                self.spawn_visitor();
+            }
+            "visitor_leaves" => {
+                // Might've been removed already, or moved in
+                let visiting = event.subject.filter(|uuid| {
+                    self.people.contains_key(uuid) && self.household_of(uuid).is_none()
+                });
+                if let Some(uuid) = visiting {
+                    self.remove_person(uuid);
+                }
+            }
+            key => godot_warn!("Unknown scheduled event `{key}`"),
+        }
+    }
+
+    /// One fixed step of the simulation. The world must not be bound by the caller.
+    fn sim_tick(mut world: Gd<World>) {
+        let people = {
+            let mut world = world.bind_mut();
+            world.advance_clock(SIM_STEP);
+            world.people_ordered()
+        };
+        for (_, mut person) in people {
+            person.bind_mut().tick(SIM_STEP);
+        }
+        world.bind_mut().check_critical_needs();
+    }
+
+    /// Everyone in the active household is sleeping, or out cold.
+    fn is_household_asleep(&self) -> bool {
+        self.active_household()
+            .map(|h| h.members().to_vec())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|uuid| self.get_person(uuid))
+            .all(|person| person.bind().is_asleep())
+    }
+
+    /// Stop ultra speed if anyone playable is in trouble.
+    fn check_critical_needs(&mut self) {
+        if self.time_scale != TimeScale::Ultra {
+            return;
+        }
+        let critical = self
+            .people_ordered()
+            .into_iter()
+            .find_map(|(uuid, person)| {
+                if !self.is_playable(&uuid) {
+                    return None;
+                }
+                let need = person.bind().needs().most_urgent(CRITICAL_NEED)?;
+                Some(WakeReason::CriticalNeed {
+                    name: person.bind().profile().full_name(),
+                    need,
+                })
+            });
+        if let Some(reason) = critical {
+            self.wake(reason);
+        }
+    }
+
+    /// Back to regular speed if skipping ahead, telling the player why.
+    pub fn wake(&mut self, reason: WakeReason) {
+        if self.time_scale != TimeScale::Ultra {
+            return;
+        }
+        self.set_time_scale(TimeScale::Regular);
+
+        let label = reason.to_label();
+        self.ui_taskbar.bind_mut().set_notice(&label);
+        // Usually woken mid-tick, while World is bound
+        self.base_mut().call_deferred(
+            "emit_signal",
+            &["sig_woke_up".to_variant(), label.to_variant()],
+        );
+    }
+
+    fn advance_clock(&mut self, delta: f64) {
+        let tick = self.clock.advance(delta);
+        if tick.days().next().is_some() {
+            self.roll_weather();
+        }
+        if !tick.hours.is_empty() {
+            self.update_environment();
+        }
+        self.emit_clock_tick(&tick);
+        for event in self.scheduler.take_due(self.clock.total_minutes()) {
+            self.on_scheduled_event(&event);
+        }
+    }
+
+    /// World is bound mid-tick, so the signals are deferred for handlers to be able to use it.
+    fn emit_clock_tick(&mut self, tick: &ClockTick) {
+        // A big step can cross several boundaries, emit each in order
+        for hour in &tick.hours {
+            if hour % 24 == MORNING_HOUR && self.is_household_asleep() {
+                self.wake(WakeReason::Morning);
+            }
+            let (day, hour) = ((hour / 24) as i64, (hour % 24) as i64);
+            if hour == 0 {
+                self.base_mut().call_deferred(
+                    "emit_signal",
+                    &["sig_day_started".to_variant(), day.to_variant()],
+                );
+            }
+            self.base_mut().call_deferred(
+                "emit_signal",
+                &[
+                    "sig_hour_started".to_variant(),
+                    day.to_variant(),
+                    hour.to_variant(),
+                ],
+            );
+        }
+    }
+
+    pub fn to_save(&self) -> WorldSave {
+        WorldSave {
+            time_of_day: self.clock.time_of_day().as_secs_f64(),
+            day: self.clock.day(),
+            minutes_per_second: self.clock.minutes_per_second(),
+            schedule: self.scheduler.clone(),
+            weather: self.weather(),
+            autonomy: self.autonomy,
+            people: self
+                .people_ordered()
+                .iter()
+                .map(|(_, p)| p.bind().to_save())
+                .collect(),
+            households: self.households.iter().map(Household::to_save).collect(),
+            active_household: self.active_household().map(|h| h.name.clone()),
+            camera_bookmarks: self.camera_bookmarks.clone(),
+            walls: self
+                .data_walls
+                .spans()
+                .into_iter()
+                .map(|(a, b)| [[a.x, a.y], [b.x, b.y]])
+                .collect(),
+            puddles: self
+                .puddles()
+                .iter()
+                .map(|p| p.get_global_position())
+                .map(|p| [p.x, p.y, p.z])
+                .collect(),
+            lit_lamps: self
+                .furniture
+                .iter()
+                .filter(|f| f.bind().is_lit())
+                .map(|f| f.get_global_position())
+                .map(|p| [p.x, p.y, p.z])
+                .collect(),
+        }
+    }
+
+    /// People are matched by uuid. Whoever isn't in the save leaves, whoever is missing is
+    /// brought back.
+    pub fn apply_save(&mut self, save: &WorldSave) {
+        self.clock
+            .set_time(save.day, Duration::from_secs_f64(save.time_of_day));
+        self.set_minutes_per_second(save.minutes_per_second);
+        self.scheduler = save.schedule.clone();
+        self.set_autonomy(save.autonomy);
+        self.set_weather(save.weather);
+        self.update_terrain_season();
+        self.apply_lot_save(save);
+
+        let saved_uuids: Vec<Uuid> = save.people.iter().map(|p| p.uuid).collect();
+        let gone: Vec<Uuid> = self
+            .people_order
+            .iter()
+            .filter(|uuid| !saved_uuids.contains(uuid))
+            .copied()
+            .collect();
+        for uuid in gone {
+            self.remove_person(uuid);
+        }
+
+        for person_save in &save.people {
+            let mut person = match self.get_person(&person_save.uuid) {
+                Some(person) => person.clone(),
+                None => self.spawn_saved_person(person_save),
+            };
+            person.bind_mut().apply_save(person_save);
+        }
+        // Same order as when saved, for the same story
+        self.people_order
+            .sort_by_key(|uuid| saved_uuids.iter().position(|u| u == uuid));
+
+        // Households are matched by name
+        for household_save in &save.households {
+            let index = match self
+                .households
+                .iter()
+                .position(|h| h.name == household_save.name)
+            {
+                Some(index) => index,
+                None => {
+                    let household =
+                        Household::new(&household_save.name, household_save.lot.as_deref());
+                    self.households.push(household);
+                    self.households.len() - 1
+                }
+            };
+            let members = household_save
+                .members
+                .iter()
+                .filter(|uuid| self.people.contains_key(uuid))
+                .copied()
+                .collect();
+
+            let household = &mut self.households[index];
+            household.funds = household_save.funds;
+            household.lot = household_save.lot.clone();
+            household.set_members(members);
+        }
+        let active = save.active_household.as_ref().and_then(|name| {
+            self.households
+                .iter()
+                .find(|h| h.name == *name)
+                .map(|h| h.uuid())
+        });
+        if save.active_household.is_some() {
+            self.set_active_household(active);
+        }
+        self.refresh_household_ui();
+        self.camera_bookmarks = save.camera_bookmarks.clone();
+    }
+
+    /// Ctrl+number stores the camera view of this lot, number alone recalls it.
+    fn process_camera_bookmarks(&mut self) {
+        let input = Input::singleton();
+        let Some(slot) = (1..=CameraBookmarks::SLOTS)
+            .find(|slot| input.is_action_just_pressed(&format!("camera_bookmark_{slot}")))
+        else {
+            return;
+        };
+
+        if input.is_action_pressed("camera_mod_bookmark_store") {
+            let bookmark = self.scn_camera_rig.bind().bookmark();
+            self.camera_bookmarks
+                .entry(LOT_NAME.into())
+                .or_default()
+                .set(slot, bookmark);
+            self.ui_taskbar
+                .bind_mut()
+                .set_notice(&format!("Stored view {slot}"));
+        } else if let Some(bookmark) = self
+            .camera_bookmarks
+            .get(LOT_NAME)
+            .and_then(|bookmarks| bookmarks.get(slot))
+        {
+            self.scn_camera_rig.bind_mut().go_to_bookmark(bookmark);
+        }
+    }
+
+    pub fn save_game(&self) {
+        let json = match self.to_save().to_json() {
+            Ok(json) => json,
+            Err(e) => {
+                godot_error!("Failed to serialize save: {e}");
+                return;
+            }
+        };
+        let Some(mut file) = FileAccess::open(SAVE_PATH, ModeFlags::WRITE) else {
+            godot_error!("Failed to open `{SAVE_PATH}` for writing");
+            return;
+        };
+        file.store_string(&json);
+        godot_print!("Saved game to `{SAVE_PATH}`");
+    }
+
+    pub fn load_game(&mut self) {
+        if !FileAccess::file_exists(SAVE_PATH) {
+            godot_warn!("No save at `{SAVE_PATH}`");
+            return;
+        }
+        let json = FileAccess::get_file_as_string(SAVE_PATH).to_string();
+        match WorldSave::from_json(&json) {
+            Ok(save) => {
+                self.apply_save(&save);
+                godot_print!("Loaded game from `{SAVE_PATH}`");
+            }
+            Err(e) => godot_error!("Failed to load save: {e}"),
+        }
+    }
+
     fn setup_ui(&mut self) {
         // Empty space above taskbar
         let mut spacer = Control::new_alloc();
@@ -236,6 +911,12 @@ impl World {
         self.ui_taskbar
             .set_anchors_preset(LayoutPreset::BOTTOM_LEFT);
         self.ui_taskbar.set_name("ui_taskbar");
+        let on_autonomy_changed = self.to_gd().callable("on_autonomy_changed");
+        self.ui_taskbar
+            .connect("sig_autonomy_changed", &on_autonomy_changed);
+        let on_portrait_pressed = self.to_gd().callable("on_portrait_pressed");
+        self.ui_taskbar
+            .connect("sig_portrait_pressed", &on_portrait_pressed);
 
         let mut ui_root = self.ui_root.clone();
         ui_root.set_anchors_preset(LayoutPreset::FULL_RECT);
@@ -249,6 +930,8 @@ impl World {
         ui_modehelp.bind_mut().add_key("F1".into(), "Play mode");
         ui_modehelp.bind_mut().add_key("F2".into(), "Buy mode");
         ui_modehelp.bind_mut().add_key("F3".into(), "Build mode");
+        ui_modehelp.bind_mut().add_key("F5".into(), "Quicksave");
+        ui_modehelp.bind_mut().add_key("F9".into(), "Quickload");
         ui_modehelp.set_name("ui_debug_ovl");
 
         let mut ui_camhelp = UiDebugOvl::new_alloc();
@@ -260,6 +943,15 @@ impl World {
             .bind_mut()
             .add_key("WASD | arrows".into(), "Move");
         ui_camhelp.bind_mut().add_key("scroll | Z/X".into(), "Zoom");
+        ui_camhelp
+            .bind_mut()
+            .add_key("double-click".into(), "Focus");
+        ui_camhelp
+            .bind_mut()
+            .add_key("F".into(), "Follow selected character");
+        ui_camhelp
+            .bind_mut()
+            .add_key("1-9 | ctrl+1-9".into(), "Recall/store view, build & buy");
         ui_camhelp.set_name("ui_controls_cam");
 
         let mut ui_playhelp = UiDebugOvl::new_alloc();
@@ -267,12 +959,25 @@ impl World {
         ui_playhelp
             .bind_mut()
             .add_key("LMB".into(), "Select character");
+        ui_playhelp
+            .bind_mut()
+            .add_key("LMB object".into(), "Action menu");
         ui_playhelp
             .bind_mut()
             .add_key("TAB".into(), "Cycle characters");
+        ui_playhelp
+            .bind_mut()
+            .add_key("C".into(), "Create character");
+        ui_playhelp.bind_mut().add_key("V".into(), "Spawn visitor");
+        ui_playhelp
+            .bind_mut()
+            .add_key("Delete".into(), "Remove character");
         ui_playhelp
             .bind_mut()
             .add_key("1/2/3".into(), "Regular/Fast/Superfast times");
+        ui_playhelp
+            .bind_mut()
+            .add_key("4".into(), "Skip ahead until something happens");
         ui_playhelp
             .bind_mut()
             .add_key("space".into(), "Toggle pause");
@@ -294,8 +999,26 @@ impl World {
         ui_debug_root.set_process_mode(ProcessMode::ALWAYS);
         ui_debug_root.set_name("ui_debug_root");
 
+        let mut ui_pie_menu = self.ui_pie_menu.clone();
+        ui_pie_menu.connect(
+            "sig_entry_picked",
+            &self.to_gd().callable("on_pie_menu_entry_picked"),
+        );
+        ui_pie_menu.set_process_mode(ProcessMode::ALWAYS);
+        ui_pie_menu.set_name("ui_pie_menu");
+
         self.base_mut().add_child(&ui_root);
         self.base_mut().add_child(&ui_debug_root);
+        self.base_mut().add_child(&ui_pie_menu);
+
+        let mut ui_character_creator = self.ui_character_creator.clone();
+        ui_character_creator.connect(
+            "sig_person_created",
+            &self.to_gd().callable("on_person_created"),
+        );
+        ui_character_creator.set_process_mode(ProcessMode::ALWAYS);
+        ui_character_creator.set_name("ui_character_creator");
+        self.base_mut().add_child(&ui_character_creator);
 
         // Initialize with none state
         self.select_person(None);
@@ -348,7 +1071,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "make_food".into(),
-                source_node: stove_ref,
+                source: ActionAdvertisementSource::Furniture(stove_ref),
                 stats: vec![
                     ActionAdvertisementStat {
                         key: "hunger".into(),
@@ -378,7 +1101,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "sleep".into(),
-                source_node: bed_ref,
+                source: ActionAdvertisementSource::Furniture(bed_ref),
                 stats: vec![ActionAdvertisementStat {
                     key: "sleep".into(),
                     value: 10,
@@ -402,7 +1125,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "sleep".into(),
-                source_node: bed_ref,
+                source: ActionAdvertisementSource::Furniture(bed_ref),
                 stats: vec![ActionAdvertisementStat {
                     key: "sleep".into(),
                     value: 10,
@@ -414,7 +1137,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "do_the_mario".into(),
-                source_node: bed_ref,
+                source: ActionAdvertisementSource::Furniture(bed_ref),
                 stats: vec![
                     ActionAdvertisementStat {
                         key: "social".into(),
@@ -443,7 +1166,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "sit".into(),
-                source_node: toilet_ref,
+                source: ActionAdvertisementSource::Furniture(toilet_ref),
                 stats: vec![ActionAdvertisementStat {
                     key: "comfort".into(),
                     value: 3,
@@ -456,7 +1179,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "toilet".into(),
-                source_node: toilet_ref,
+                source: ActionAdvertisementSource::Furniture(toilet_ref),
                 stats: vec![ActionAdvertisementStat {
                     key: "bladder".into(),
                     value: 10,
@@ -478,7 +1201,7 @@ impl World {
             .available_actions_mut()
             .push(ActionAdvertisement {
                 action_key: "wash_hands".into(),
-                source_node: sink_ref,
+                source: ActionAdvertisementSource::Furniture(sink_ref),
                 stats: vec![ActionAdvertisementStat {
                     key: "hygiene".into(),
                     value: 10,
@@ -486,6 +1209,35 @@ impl World {
                 required_people: 1,
             });
 
+        let mut table_coll_box = BoxShape3D::new_gd();
+        table_coll_box.set_size(Vector3::new(1.0, 1.0, 1.0));
+        let table_coll_offset = table_coll_box.get_size() / 2.0;
+        let mut table = Furniture::new(
+            "res://assets/furniture/mdl_counter_floor_1x1_001.blend",
+            table_coll_box.upcast::<Shape3D>(),
+            table_coll_offset,
+            vec![],
+        );
+        let table_ref = table.clone();
+        table
+            .bind_mut()
+            .available_actions_mut()
+            .push(ActionAdvertisement {
+                action_key: "eat_together".into(),
+                source: ActionAdvertisementSource::Furniture(table_ref),
+                stats: vec![
+                    ActionAdvertisementStat {
+                        key: "hunger".into(),
+                        value: 8,
+                    },
+                    ActionAdvertisementStat {
+                        key: "social".into(),
+                        value: 6,
+                    },
+                ],
+                required_people: 4,
+            });
+
         stove.set_position(Vector3::new(8.0, 0.0, 15.0));
         bed.set_position(Vector3::new(11.0, 0.0, 15.0));
         bed2.set_position(Vector3::new(11.0, 0.0, 20.0));
@@ -496,74 +1248,448 @@ impl World {
         self.add_furniture(bed2);
         self.add_furniture(toilet);
         self.add_furniture(sink);
+        table.set_position(Vector3::new(7.0, 0.0, 20.0));
+        self.add_furniture(table);
+
+        for (kind, position) in [
+            (LampKind::Ceiling, Vector3::new(9.0, 0.0, 18.0)),
+            (LampKind::Floor, Vector3::new(10.3, 0.0, 15.3)),
+            (LampKind::Table, Vector3::new(12.0, 0.0, 15.0)),
+        ] {
+            let mut lamp = Furniture::new_lamp(kind);
+            lamp.set_position(position);
+            self.add_furniture(lamp);
+        }
     }
 
     fn setup_people(&mut self) {
-        let mut alice = Person::new(self.to_gd());
+        let mut alice = Person::new(self.to_gd(), PersonProfile::new("Alice", "Smith"));
         alice.set_position(Vector3::new(5.0, 0.0, 10.0));
-        alice.set_name("alice");
 
-        let mut bob = Person::new(self.to_gd());
+        let bob_profile = PersonProfile {
+            skin_color: [0.78, 0.58, 0.42],
+            hair_color: [0.35, 0.22, 0.12],
+            ..PersonProfile::new("Bob", "Smith")
+        };
+        let mut bob = Person::new(self.to_gd(), bob_profile);
         bob.set_position(Vector3::new(7.0, 0.0, 7.0));
-        bob.set_name("bob");
+
+        let carol_profile = PersonProfile {
+            traits: vec![PersonTrait::Outgoing],
+            hair_color: [0.6, 0.2, 0.08],
+            ..PersonProfile::new("Carol", "Jones")
+        };
+        let mut carol = Person::new(self.to_gd(), carol_profile);
+        carol.set_position(Vector3::new(9.0, 0.0, 9.0));
+
+        let mut smiths = Household::new("Smith", Some(LOT_NAME));
+        smiths.add_member(alice.bind().uuid());
+        smiths.add_member(bob.bind().uuid());
+        let smiths_uuid = smiths.uuid();
 
         self.add_person(alice);
         self.add_person(bob);
+        // Carol lives elsewhere and drops by
+        self.add_person(carol);
+
+        self.add_household(smiths);
+        self.set_active_household(Some(smiths_uuid));
+    }
+
+    fn setup_schedule(&mut self) {
+        let now = self.clock.total_minutes();
+        self.scheduler
+            .schedule_weekly("bills", now, Weekday::Monday, 9, 0);
+        self.scheduler.schedule_daily("visitor", now, 18, 0);
     }
 
     pub fn add_person(&mut self, mut person: Gd<Person>) {
         person.connect("sig_selected", &self.to_gd().callable("on_person_selected"));
         let uuid = person.bind().uuid();
+        person.bind_mut().seed_rng(self.rng.random());
 
         self.scn_root.add_child(&person);
         self.people.insert(uuid, person);
+        self.people_order.push(uuid);
     }
 
-    pub fn add_furniture(&mut self, furniture: Gd<Furniture>) {
-        //furniture.connect("sig_selected", &self.to_gd().callable("on_person_selected"));
+    /// Take a person out of the world. Their tasks end and company is let go of on the next idle
+    /// frame, when the world isn't busy. Returns false if there's no such person.
+    pub fn remove_person(&mut self, uuid: Uuid) -> bool {
+        let Some(mut person) = self.people.remove(&uuid) else {
+            return false;
+        };
+
+        if self.selected_person.as_ref() == Some(&person) {
+            self.select_person(None);
+        }
+        self.people_order.retain(|u| *u != uuid);
+        for household in &mut self.households {
+            household.remove_member(&uuid);
+        }
+        self.refresh_household_ui();
+
+        person.set_process_mode(ProcessMode::DISABLED);
+        person.call_deferred("leave_world", &[]);
+        true
+    }
+
+    /// Walls, puddles and lamps.
+    fn apply_lot_save(&mut self, save: &WorldSave) {
+        // The lot builder keeps its own copy of the walls
+        if self.view_mode == WorldViewMode::Build {
+            self.set_view_mode(WorldViewMode::Play);
+            self.scn_lot_walls.show();
+        }
+        self.data_walls = lot_data::Walls::from_spans(
+            save.walls
+                .iter()
+                .map(|[a, b]| (Vector2i::new(a[0], a[1]), Vector2i::new(b[0], b[1]))),
+        );
+        self.rebuild_building_mesh();
+
+        for puddle in self.puddles() {
+            self.remove_furniture(&puddle);
+        }
+        for [x, y, z] in &save.puddles {
+            self.spawn_puddle(Vector3::new(*x, *y, *z));
+        }
+
+        for lamp in &mut self.furniture {
+            let position = lamp.get_global_position();
+            let lit = save
+                .lit_lamps
+                .iter()
+                .any(|[x, y, z]| Vector3::new(*x, *y, *z).distance_to(position) < 0.01);
+            let mut lamp = lamp.bind_mut();
+            if lamp.is_lamp() {
+                lamp.set_lit(lit);
+            }
+        }
+        self.update_environment();
+    }
+
+    /// Messes waiting to be cleaned up.
+    fn puddles(&self) -> Vec<Gd<Furniture>> {
+        self.furniture
+            .iter()
+            .filter(|f| {
+                f.bind()
+                    .available_actions()
+                    .iter()
+                    .any(|a| a.action_key == "clean_puddle")
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Bring back someone from a save who isn't in the world.
+    fn spawn_saved_person(&mut self, save: &PersonSave) -> Gd<Person> {
+        let person = Person::with_uuid(self.to_gd(), save.uuid, save.profile.clone());
+        self.add_person(person.clone());
+        person
+    }
+
+    /// Someone drops by, arriving at a random spot on the lot edge. They leave after a while.
+    pub fn spawn_visitor(&mut self) -> Gd<Person> {
+        let along = self.rng.random_range(0.0..LOT_SIZE);
+        let position = match self.rng.random_range(0..4) {
+            0 => Vector3::new(along, 0.0, 0.0),
+            1 => Vector3::new(along, 0.0, LOT_SIZE),
+            2 => Vector3::new(0.0, 0.0, along),
+            _ => Vector3::new(LOT_SIZE, 0.0, along),
+        };
+
+        let profile = PersonProfile::random(&mut self.rng);
+        let mut visitor = Person::new(self.to_gd(), profile);
+        visitor.set_position(position);
+
+        self.add_person(visitor.clone());
+        let uuid = visitor.bind().uuid();
+        let leave_at = self.clock.total_minutes() + VISIT_MINUTES;
+        self.scheduler
+            .schedule_for("visitor_leaves", leave_at, uuid);
+        visitor
+    }
+
+    pub fn add_furniture(&mut self, mut furniture: Gd<Furniture>) {
+        furniture.connect(
+            "sig_selected",
+            &self.to_gd().callable("on_furniture_selected"),
+        );
 
         self.scn_root.add_child(&furniture);
         self.furniture.push(furniture);
+        self.update_environment();
+    }
+
+    /// Is `furniture` still part of the lot, and not on its way out.
+    pub fn has_furniture(&self, furniture: &Gd<Furniture>) -> bool {
+        self.furniture.contains(furniture)
+    }
+
+    /// Take `furniture` off the lot and free it. People with actions on it drop them on their own.
+    pub fn remove_furniture(&mut self, furniture: &Gd<Furniture>) {
+        self.furniture.retain(|f| f != furniture);
+        furniture.clone().queue_free();
+        self.update_environment();
+    }
+
+    /// Leave a mess on the floor. Someone will have to clean it up.
+    pub fn spawn_puddle(&mut self, position: Vector3) {
+        let mut coll_box = BoxShape3D::new_gd();
+        coll_box.set_size(Vector3::new(0.8, 0.05, 0.8));
+        let coll_offset = Vector3::UP * coll_box.get_size().y / 2.0;
+
+        let mut puddle = Furniture::new(
+            "res://assets/prefabs/mdl_puddle.tscn",
+            coll_box.upcast::<Shape3D>(),
+            coll_offset,
+            vec![],
+        );
+        let puddle_ref = puddle.clone();
+        puddle.bind_mut().set_decor(-0.5);
+        puddle
+            .bind_mut()
+            .available_actions_mut()
+            .push(ActionAdvertisement {
+                action_key: "clean_puddle".into(),
+                source: ActionAdvertisementSource::Furniture(puddle_ref),
+                stats: vec![ActionAdvertisementStat {
+                    key: "environment".into(),
+                    value: 6,
+                }],
+                required_people: 1,
+            });
+
+        puddle.set_position(position);
+        self.add_furniture(puddle);
+    }
+
+    /// 0.0..=1.0, environment score of a spot. Cached per room, see [Self::update_environment].
+    pub fn environment_at(&self, position: Vector3) -> f64 {
+        match self.room_at(position) {
+            Some(room) => self
+                .room_environment
+                .get(room)
+                .copied()
+                .unwrap_or(self.outdoor_environment),
+            None => self.outdoor_environment,
+        }
+    }
+
+    /// What makes a room nice or not: everything in it, and how bright it is in the middle.
+    /// None is outdoors, where the whole yard counts.
+    pub fn environment_score_of(&self, room: Option<usize>) -> EnvironmentScore {
+        let decor = self
+            .furniture
+            .iter()
+            .filter(|f| self.room_at(f.get_global_position()) == room)
+            .map(|f| f.bind().decor());
+        let brightness = match room.and_then(|r| self.data_rooms.room_center(r)) {
+            Some(center) => self.brightness_at(center),
+            None => self.scn_env.bind().daylight() as f64,
+        };
+
+        EnvironmentScore::new(
+            decor,
+            brightness,
+            room.map(|r| self.data_rooms.room_size(r)),
+            self.outdoor_comfort(),
+        )
+    }
+
+    /// 0.0..=1.0, how pleasant it is to be outside right now.
+    pub fn outdoor_comfort(&self) -> f64 {
+        let env = self.scn_env.bind();
+        env.daylight() as f64 * env.weather().outdoor_comfort()
+    }
+
+    pub fn weather(&self) -> Weather {
+        self.scn_env.bind().weather()
+    }
+
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.scn_env.bind_mut().set_weather(weather);
+        self.update_environment();
+    }
+
+    /// New day, new weather. Also keeps the ground in season.
+    fn roll_weather(&mut self) {
+        let weather = Weather::random(self.clock.season(), &mut self.rng);
+        self.set_weather(weather);
+        self.update_terrain_season();
+    }
+
+    /// Grass goes dull in autumn and winter.
+    fn update_terrain_season(&mut self) {
+        let Some(terrain) = self.scn_root.get_node_or_null("terrain") else {
+            return;
+        };
+        let grass_default: Gd<Material> =
+            load("res://assets/materials/mat_ground_grass_default.tres");
+        let grass_secondary: Gd<Material> =
+            load("res://assets/materials/mat_ground_grass_secondary.tres");
+        let dull = matches!(self.clock.season(), Season::Autumn | Season::Winter);
+
+        let meshes = terrain
+            .find_children_ex("*")
+            .type_("MeshInstance3D")
+            .owned(false)
+            .done();
+        for mesh in meshes.iter_shared() {
+            let mut mesh = mesh.cast::<MeshInstance3D>();
+            let Some(surfaces) = mesh.get_mesh() else {
+                continue;
+            };
+            for i in 0..surfaces.get_surface_count() {
+                if surfaces.surface_get_material(i).as_ref() != Some(&grass_default) {
+                    continue;
+                }
+                if dull {
+                    mesh.set_surface_override_material(i, &grass_secondary);
+                } else {
+                    mesh.set_surface_override_material(i, Gd::null_arg());
+                }
+            }
+        }
+    }
+
+    /// Switch a lamp, and let the room know it's brighter or darker now.
+    pub fn set_lamp_lit(&mut self, lamp: &mut Gd<Furniture>, lit: bool) {
+        lamp.bind_mut().set_lit(lit);
+        self.update_environment();
+    }
+
+    /// Score every room again, and let furniture know how nice its spot is, for people picking
+    /// where to go. Needed hourly, as the daylight changes, and whenever walls, furniture,
+    /// lamps or weather do.
+    fn update_environment(&mut self) {
+        self.room_environment = (0..self.data_rooms.room_count())
+            .map(|room| self.environment_score_of(Some(room)).total())
+            .collect();
+        self.outdoor_environment = self.environment_score_of(None).total();
+
+        let scores: Vec<f64> = self
+            .furniture
+            .iter()
+            .map(|f| self.environment_at(f.get_global_position()))
+            .collect();
+        for (furniture, score) in self.furniture.iter_mut().zip(scores) {
+            furniture.bind_mut().set_environment(score);
+        }
+    }
+
+    /// Room at a spot, None if outdoors.
+    pub fn room_at(&self, position: Vector3) -> Option<usize> {
+        self.data_rooms.room_at(position)
+    }
+
+    /// 0.0..=1.0, light from the sky only. Indoors gets less of it.
+    pub fn ambient_brightness_at(&self, position: Vector3) -> f64 {
+        let daylight = self.scn_env.bind().daylight() as f64;
+        match self.room_at(position) {
+            Some(_) => daylight * INDOOR_DAYLIGHT,
+            None => daylight,
+        }
+    }
+
+    /// 0.0..=1.0, light from the sky and lamps in the same room.
+    pub fn brightness_at(&self, position: Vector3) -> f64 {
+        let room = self.room_at(position);
+        let lamps: f64 = self
+            .furniture
+            .iter()
+            .filter(|f| self.room_at(f.get_global_position()) == room)
+            .map(|f| f.bind().brightness_at(position))
+            .sum();
+        (self.ambient_brightness_at(position) + lamps).min(1.0)
     }
 
     pub fn select_person(&mut self, person: Option<Gd<Person>>) {
+        {
+            let mut camera_rig = self.scn_camera_rig.bind_mut();
+            if let Some(person) = &person {
+                camera_rig.focus_on(person.get_global_position());
+            }
+            camera_rig.set_follow_target(person.clone().map(|p| p.upcast()));
+        }
         self.selected_person = person.clone();
         self.ui_taskbar.bind_mut().select_person(person);
         self.spirit_level.bind_mut().target = self.selected_person.clone();
     }
 
+    /// Cycle through members of the active household.
     pub fn select_next_person(&mut self) {
-        let Some(current) = self.selected_person.as_ref().map(|p| p.bind().uuid()) else {
-            if let Some(person) = self.people.values().next() {
-                self.select_person(Some(person.clone()));
-            }
-            return;
-        };
-        let mut prev = Uuid::nil();
-        for person in self.people.values() {
-            if prev == current {
-                self.select_person(Some(person.clone()));
-                return;
-            }
-            prev = person.bind().uuid()
+        let current = self.selected_person.as_ref().map(|p| p.bind().uuid());
+        let next = self
+            .active_household()
+            .and_then(|h| h.next_member(current))
+            .and_then(|uuid| self.get_person(&uuid).cloned());
+        if let Some(person) = next {
+            self.select_person(Some(person));
         }
-        for person in self.people.values() {
-            if prev == current {
-                self.select_person(Some(person.clone()));
-                return;
-            }
-            prev = person.bind().uuid()
+    }
+
+    /// Is the cursor over something clickable, like a person or furniture.
+    fn is_entity_hovered(&self) -> bool {
+        self.hovered_entity_position().is_some()
+    }
+
+    /// Where the cursor ray hits something clickable.
+    fn hovered_entity_position(&self) -> Option<Vector3> {
+        let viewport = self.base().get_viewport()?;
+        let camera = viewport.get_camera_3d()?;
+        let mut space = viewport
+            .find_world_3d()
+            .and_then(|mut w| w.get_direct_space_state())?;
+
+        let mouse_pos = viewport.get_mouse_position();
+        let from = camera.project_ray_origin(mouse_pos);
+        let to = from + camera.project_ray_normal(mouse_pos) * 1024.0;
+        let mut query = PhysicsRayQueryParameters3D::create(from, to)?;
+        query.set_collide_with_areas(true);
+
+        let hit = space.intersect_ray(&query);
+        hit.get("position").map(|p| p.to::<Vector3>())
+    }
+
+    /// Where the cursor points: something clickable, or the ground.
+    fn hovered_position(&self) -> Option<Vector3> {
+        if let Some(position) = self.hovered_entity_position() {
+            return Some(position);
         }
+        let viewport = self.base().get_viewport()?;
+        let camera = viewport.get_camera_3d()?;
+        let mouse_pos = viewport.get_mouse_position();
+        let from = camera.project_ray_origin(mouse_pos);
+        let dir = camera.project_ray_normal(mouse_pos);
+        if dir.y >= 0.0 {
+            return None;
+        }
+        Some(from + dir * (-from.y / dir.y))
     }
 
     fn rebuild_building_mesh(&mut self) {
+        self.data_rooms = lot_data::RoomMap::from_walls(&self.data_walls);
         let mesh = self.data_walls.to_mesh();
         self.scn_lot_walls.set_mesh(&mesh);
+        self.update_environment();
     }
 
+    /// Unpauses in play mode. Elsewhere the world stays paused until back in play.
     fn set_time_scale(&mut self, time_scale: TimeScale) {
-        self.base_mut().get_tree().unwrap().set_pause(false);
-        Engine::singleton().set_time_scale(time_scale.to_engine_time());
+        if self.view_mode == WorldViewMode::Play {
+            self.base_mut().get_tree().unwrap().set_pause(false);
+        }
+
+        // Nobody's watching at ultra speed, save the effort of drawing
+        let ultra = time_scale == TimeScale::Ultra;
+        if let Some(mut viewport) = self.base().get_viewport() {
+            viewport.set_disable_3d(ultra);
+        }
+        let notice = if ultra { "Skipping ahead..." } else { "" };
+        self.ui_taskbar.bind_mut().set_notice(notice);
 
         self.time_scale = time_scale
     }
@@ -5,14 +5,51 @@
 use std::f32::consts::PI;
 use std::time::Duration;
 
-use godot::classes::DirectionalLight3D;
+use godot::classes::{
+    environment::{AmbientSource, BgMode},
+    light_3d::Param as LightParam,
+    Curve, DirectionalLight3D, Environment, GpuParticles3D, Gradient, ProceduralSkyMaterial, Sky,
+    WorldEnvironment,
+};
 use godot::obj::WithBaseField;
 use godot::prelude::*;
 
+use crate::Weather;
+
+/// Highest the sun and moon get, in radians above the horizon.
+const MAX_ELEVATION: f32 = PI * 0.38;
+/// Rotates the sun's path so it doesn't line up with the lot walls.
+const PATH_YAW: f32 = -0.42;
+const SUNRISE_HOUR: f32 = 6.0;
+const MOON_ENERGY: f32 = 0.15;
+
+/// Where rain and snow fall from, above the middle of the lot.
+const PRECIPITATION_ORIGIN: Vector3 = Vector3::new(16.0, 15.0, 16.0);
+
+/// Keyframes across the day, offset 0.0 is midnight and 1.0 the next midnight.
+const DAY_OFFSETS: [f32; 7] = [0.0, 0.22, 0.27, 0.5, 0.73, 0.78, 1.0];
+
 #[derive(Debug, GodotClass)]
 #[class(base=Node)]
 pub struct WorldEnv {
     sun: Gd<DirectionalLight3D>,
+    moon: Gd<DirectionalLight3D>,
+    world_environment: Gd<WorldEnvironment>,
+    environment: Gd<Environment>,
+    sky_material: Gd<ProceduralSkyMaterial>,
+    vfx_rain: Gd<GpuParticles3D>,
+    vfx_snow: Gd<GpuParticles3D>,
+
+    curve_sun_energy: Gd<Curve>,
+    curve_ambient_energy: Gd<Curve>,
+    gradient_sun: Gd<Gradient>,
+    gradient_sky_top: Gd<Gradient>,
+    gradient_sky_horizon: Gd<Gradient>,
+    gradient_ambient: Gd<Gradient>,
+
+    weather: Weather,
+    /// 0.0..=1.0, how much the sun lights things up right now.
+    daylight: f32,
 
     base: Base<Node>,
 }
@@ -22,6 +59,24 @@ impl INode for WorldEnv {
     fn init(base: Base<Self::Base>) -> Self {
         Self {
             sun: DirectionalLight3D::new_alloc(),
+            moon: DirectionalLight3D::new_alloc(),
+            world_environment: WorldEnvironment::new_alloc(),
+            environment: Environment::new_gd(),
+            sky_material: ProceduralSkyMaterial::new_gd(),
+            vfx_rain: load::<PackedScene>("res://assets/prefabs/vfx_particle_rain.tscn")
+                .instantiate_as::<GpuParticles3D>(),
+            vfx_snow: load::<PackedScene>("res://assets/prefabs/vfx_particle_snow.tscn")
+                .instantiate_as::<GpuParticles3D>(),
+
+            curve_sun_energy: Curve::new_gd(),
+            curve_ambient_energy: Curve::new_gd(),
+            gradient_sun: Gradient::new_gd(),
+            gradient_sky_top: Gradient::new_gd(),
+            gradient_sky_horizon: Gradient::new_gd(),
+            gradient_ambient: Gradient::new_gd(),
+
+            weather: Weather::default(),
+            daylight: 1.0,
 
             base,
         }
@@ -31,8 +86,7 @@ impl INode for WorldEnv {
         self.setup_env();
 
         // Vaguely just after midday-ish
-        self.sun
-            .set_rotation_degrees(Vector3::new(-56.8, -24.1, 28.2));
+        self.set_time(Duration::from_secs(13 * 3600));
     }
 }
 
@@ -40,12 +94,178 @@ impl WorldEnv {
     fn setup_env(&mut self) {
         let mut sun = self.sun.clone();
         sun.set_name("env_sun");
+        sun.set_shadow(true);
+
+        let mut moon = self.moon.clone();
+        moon.set_name("env_moon");
+        moon.set_color(Color::from_rgb(0.6, 0.7, 1.0));
+        moon.set_param(LightParam::ENERGY, MOON_ENERGY);
+
+        self.setup_curves();
+
+        let mut sky = Sky::new_gd();
+        sky.set_material(&self.sky_material);
+        self.environment.set_background(BgMode::SKY);
+        self.environment.set_sky(&sky);
+        self.environment.set_ambient_source(AmbientSource::COLOR);
+
+        let mut world_environment = self.world_environment.clone();
+        world_environment.set_name("env_world_environment");
+        world_environment.set_environment(&self.environment);
 
         self.base_mut().add_child(&sun);
+        self.base_mut().add_child(&moon);
+        self.base_mut().add_child(&world_environment);
+
+        for (mut vfx, name) in [
+            (self.vfx_rain.clone(), "vfx_rain"),
+            (self.vfx_snow.clone(), "vfx_snow"),
+        ] {
+            vfx.set_name(name);
+            vfx.set_position(PRECIPITATION_ORIGIN);
+            vfx.set_emitting(false);
+            self.base_mut().add_child(&vfx);
+        }
+    }
+
+    fn setup_curves(&mut self) {
+        for (curve, values) in [
+            (
+                &mut self.curve_sun_energy,
+                [0.0, 0.0, 0.6, 1.2, 0.6, 0.0, 0.0],
+            ),
+            (
+                &mut self.curve_ambient_energy,
+                [0.25, 0.25, 0.5, 0.8, 0.5, 0.25, 0.25],
+            ),
+        ] {
+            curve.set_max_value(2.0);
+            for (offset, value) in DAY_OFFSETS.into_iter().zip(values) {
+                curve.add_point(Vector2::new(offset, value));
+            }
+        }
+
+        let night = Color::from_rgb(0.02, 0.03, 0.08);
+        let dusk = Color::from_rgb(0.9, 0.45, 0.25);
+        for (gradient, colors) in [
+            (
+                &mut self.gradient_sun,
+                [
+                    dusk,
+                    dusk,
+                    Color::from_rgb(1.0, 0.75, 0.55),
+                    Color::from_rgb(1.0, 0.98, 0.92),
+                    Color::from_rgb(1.0, 0.75, 0.55),
+                    dusk,
+                    dusk,
+                ],
+            ),
+            (
+                &mut self.gradient_sky_top,
+                [
+                    night,
+                    night,
+                    Color::from_rgb(0.3, 0.4, 0.65),
+                    Color::from_rgb(0.38, 0.55, 0.85),
+                    Color::from_rgb(0.3, 0.4, 0.65),
+                    night,
+                    night,
+                ],
+            ),
+            (
+                &mut self.gradient_sky_horizon,
+                [
+                    night,
+                    Color::from_rgb(0.1, 0.1, 0.2),
+                    dusk,
+                    Color::from_rgb(0.65, 0.75, 0.85),
+                    dusk,
+                    Color::from_rgb(0.1, 0.1, 0.2),
+                    night,
+                ],
+            ),
+            (
+                &mut self.gradient_ambient,
+                [
+                    Color::from_rgb(0.25, 0.3, 0.5),
+                    Color::from_rgb(0.25, 0.3, 0.5),
+                    Color::from_rgb(0.8, 0.65, 0.6),
+                    Color::from_rgb(0.85, 0.85, 0.85),
+                    Color::from_rgb(0.8, 0.65, 0.6),
+                    Color::from_rgb(0.25, 0.3, 0.5),
+                    Color::from_rgb(0.25, 0.3, 0.5),
+                ],
+            ),
+        ] {
+            gradient.set_offsets(&PackedFloat32Array::from(&DAY_OFFSETS));
+            gradient.set_colors(&PackedColorArray::from(&colors));
+        }
+    }
+
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    /// Start or stop rain and snow. Sky and sun follow on the next [Self::set_time].
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.weather = weather;
+        self.vfx_rain.set_emitting(weather == Weather::Rain);
+        self.vfx_snow.set_emitting(weather == Weather::Snow);
+    }
+
+    /// 0.0..=1.0, how much the sun lights things up right now.
+    pub fn daylight(&self) -> f32 {
+        self.daylight
     }
 
     pub fn set_time(&mut self, time_of_day: Duration) {
-        let angle = time_of_day.as_secs_f32() / (3600.0 * 24.0) * PI * 2.0 + PI;
-        self.sun.set_rotation(Vector3::new(angle, -24.0, 0.0));
+        let hours = time_of_day.as_secs_f32() / 3600.0;
+        let offset = hours / 24.0;
+
+        // 0.0 at sunrise, 1.0 at sunset, and the moon takes the other half
+        let phase = (hours - SUNRISE_HOUR) / 12.0;
+        let sun_elevation = (phase * PI).sin() * MAX_ELEVATION;
+        let sun_up = sun_elevation > 0.0;
+        self.sun
+            .set_rotation(Vector3::new(-sun_elevation, sky_yaw(phase), 0.0));
+        self.moon
+            .set_rotation(Vector3::new(sun_elevation, sky_yaw(phase - 1.0), 0.0));
+
+        let sun_energy = if sun_up {
+            self.curve_sun_energy.sample(offset) * self.weather.sun_factor()
+        } else {
+            0.0
+        };
+        self.sun.set_param(LightParam::ENERGY, sun_energy);
+        self.sun.set_color(self.gradient_sun.sample(offset));
+        // Shadows from below the ground only make things weird
+        self.sun.set_shadow(sun_up);
+        self.moon.set_visible(!sun_up);
+
+        let cloud_cover = self.weather.cloud_cover();
+        self.sky_material
+            .set_sky_top_color(overcast(self.gradient_sky_top.sample(offset), cloud_cover));
+        let horizon = overcast(self.gradient_sky_horizon.sample(offset), cloud_cover);
+        self.sky_material.set_sky_horizon_color(horizon);
+        self.sky_material.set_ground_horizon_color(horizon);
+
+        self.environment
+            .set_ambient_light_color(self.gradient_ambient.sample(offset));
+        self.environment
+            .set_ambient_light_energy(self.curve_ambient_energy.sample(offset));
+
+        self.daylight = (sun_energy / 1.2).clamp(0.0, 1.0);
     }
 }
+
+/// Wash `color` out towards grey, as much as the sky is covered.
+fn overcast(color: Color, cloud_cover: f32) -> Color {
+    let grey = (color.r + color.g + color.b) / 3.0;
+    color.lerp(Color::from_rgb(grey, grey, grey), cloud_cover as f64)
+}
+
+/// Compass direction along the sky path, east at 0.0 and west at 1.0.
+fn sky_yaw(phase: f32) -> f32 {
+    let phase = phase.rem_euclid(2.0).min(1.0);
+    PATH_YAW + (phase - 0.5) * PI
+}